@@ -0,0 +1,37 @@
+-- Create service plans table (pricing tiers per streaming service)
+CREATE TABLE service_plans (
+    id VARCHAR(50) PRIMARY KEY,
+    service_id VARCHAR(50) NOT NULL REFERENCES streaming_services(id),
+    name VARCHAR(255) NOT NULL,
+    monthly_cost DECIMAL(10, 2) NOT NULL,
+    has_ads BOOLEAN NOT NULL,
+    max_quality VARCHAR(10) DEFAULT 'HD' NOT NULL,
+    active BOOLEAN DEFAULT true NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW() NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT NOW() NOT NULL
+);
+
+CREATE INDEX idx_service_plans_service_id ON service_plans(service_id);
+
+-- Seed US plans with current pricing (as of December 2025)
+-- streaming_services.base_monthly_cost still holds the lowest ad-free tier
+INSERT INTO service_plans (id, service_id, name, monthly_cost, has_ads, max_quality) VALUES
+    ('netflix_ads', 'netflix', 'Standard with ads', 7.99, true, 'HD'),
+    ('netflix_standard', 'netflix', 'Standard', 17.99, false, 'HD'),
+    ('netflix_premium', 'netflix', 'Premium', 24.99, false, '4K'),
+    ('hulu_ads', 'hulu', 'Hulu (With Ads)', 11.99, true, 'HD'),
+    ('hulu_no_ads', 'hulu', 'Hulu (No Ads)', 18.99, false, 'HD'),
+    ('prime_ads', 'prime', 'Prime Video (With Ads)', 8.99, true, '4K'),
+    ('prime_ad_free', 'prime', 'Prime Video (Ad-Free)', 11.98, false, '4K'),
+    ('disney_basic', 'disney', 'Basic (With Ads)', 11.99, true, 'HD'),
+    ('disney_premium', 'disney', 'Premium (No Ads)', 18.99, false, '4K'),
+    ('hbo_basic', 'hbo', 'Basic with Ads', 10.99, true, 'HD'),
+    ('hbo_standard', 'hbo', 'Standard', 18.49, false, 'HD'),
+    ('hbo_premium', 'hbo', 'Premium', 22.99, false, '4K'),
+    ('apple', 'apple', 'Apple TV+', 12.99, false, '4K'),
+    ('paramount_essential', 'paramount', 'Essential (With Ads)', 8.99, true, 'HD'),
+    ('paramount_premium', 'paramount', 'Premium', 12.99, false, '4K'),
+    ('peacock_premium', 'peacock', 'Premium (With Ads)', 10.99, true, 'HD'),
+    ('peacock_premium_plus', 'peacock', 'Premium Plus', 16.99, false, '4K'),
+    ('starz', 'starz', 'Starz', 10.99, false, '4K')
+ON CONFLICT (id) DO NOTHING;
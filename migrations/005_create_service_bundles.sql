@@ -0,0 +1,43 @@
+-- Create service bundles table (several services sold together at one price)
+CREATE TABLE service_bundles (
+    id VARCHAR(50) PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    monthly_cost DECIMAL(10, 2) NOT NULL,
+    active BOOLEAN DEFAULT true NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW() NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT NOW() NOT NULL
+);
+
+-- Each member is the plan (tier) of a service included in the bundle
+CREATE TABLE service_bundle_members (
+    bundle_id VARCHAR(50) NOT NULL REFERENCES service_bundles(id),
+    plan_id VARCHAR(50) NOT NULL REFERENCES service_plans(id),
+    PRIMARY KEY (bundle_id, plan_id)
+);
+
+CREATE INDEX idx_service_bundle_members_plan_id ON service_bundle_members(plan_id);
+
+-- Seed US bundles with current pricing (as of December 2025)
+INSERT INTO service_bundles (id, name, monthly_cost) VALUES
+    ('disney_hulu_basic', 'Disney+, Hulu Bundle Basic', 11.99),
+    ('disney_hulu_premium', 'Disney+, Hulu Bundle Premium', 26.99),
+    ('disney_hulu_max_basic', 'Disney+, Hulu, HBO Max Bundle Basic', 19.99),
+    ('disney_hulu_max_premium', 'Disney+, Hulu, HBO Max Bundle Premium', 32.99),
+    ('streamsaver', 'Xfinity StreamSaver', 15.00)
+ON CONFLICT (id) DO NOTHING;
+
+INSERT INTO service_bundle_members (bundle_id, plan_id) VALUES
+    ('disney_hulu_basic', 'disney_basic'),
+    ('disney_hulu_basic', 'hulu_ads'),
+    ('disney_hulu_premium', 'disney_premium'),
+    ('disney_hulu_premium', 'hulu_no_ads'),
+    ('disney_hulu_max_basic', 'disney_basic'),
+    ('disney_hulu_max_basic', 'hulu_ads'),
+    ('disney_hulu_max_basic', 'hbo_basic'),
+    ('disney_hulu_max_premium', 'disney_premium'),
+    ('disney_hulu_max_premium', 'hulu_no_ads'),
+    ('disney_hulu_max_premium', 'hbo_standard'),
+    ('streamsaver', 'netflix_ads'),
+    ('streamsaver', 'peacock_premium'),
+    ('streamsaver', 'apple')
+ON CONFLICT (bundle_id, plan_id) DO NOTHING;
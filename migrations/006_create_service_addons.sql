@@ -0,0 +1,28 @@
+-- Create add-on channels table (channels bought on top of a parent service)
+-- channel_id matches the Streaming Availability API add-on identifier
+CREATE TABLE service_addons (
+    id VARCHAR(50) PRIMARY KEY,
+    parent_service_id VARCHAR(50) NOT NULL REFERENCES streaming_services(id),
+    channel_id VARCHAR(50) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    monthly_cost DECIMAL(10, 2) NOT NULL,
+    has_ads BOOLEAN DEFAULT false NOT NULL,
+    active BOOLEAN DEFAULT true NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW() NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT NOW() NOT NULL,
+    UNIQUE(parent_service_id, channel_id)
+);
+
+CREATE INDEX idx_service_addons_parent_service_id ON service_addons(parent_service_id);
+
+-- Seed US add-on channels with current pricing (as of December 2025)
+INSERT INTO service_addons (id, parent_service_id, channel_id, name, monthly_cost) VALUES
+    ('prime_starz', 'prime', 'starz', 'STARZ (Prime Video Channel)', 10.99),
+    ('prime_mgmplus', 'prime', 'mgmplus', 'MGM+ (Prime Video Channel)', 6.99),
+    ('prime_amcplus', 'prime', 'amcplus', 'AMC+ (Prime Video Channel)', 8.99),
+    ('prime_britbox', 'prime', 'britbox', 'BritBox (Prime Video Channel)', 8.99),
+    ('prime_crunchyroll', 'prime', 'crunchyroll', 'Crunchyroll (Prime Video Channel)', 7.99),
+    ('apple_starz', 'apple', 'starz', 'STARZ (Apple TV Channel)', 10.99),
+    ('apple_mgmplus', 'apple', 'mgmplus', 'MGM+ (Apple TV Channel)', 6.99),
+    ('apple_amcplus', 'apple', 'amcplus', 'AMC+ (Apple TV Channel)', 8.99)
+ON CONFLICT (id) DO NOTHING;
@@ -0,0 +1,5 @@
+-- Keep the outcome of each optimization run so it can be reloaded by request ID
+-- response holds the full OptimizationResponse; error is set instead when the run failed
+ALTER TABLE optimization_requests
+    ADD COLUMN response JSONB,
+    ADD COLUMN error TEXT;
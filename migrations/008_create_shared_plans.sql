@@ -0,0 +1,16 @@
+-- Record how old the availability data behind each optimization run was
+ALTER TABLE optimization_requests
+    ADD COLUMN availability_as_of TIMESTAMPTZ;
+
+-- Create shared plans table (optimization results shared under a short link)
+-- slug is random and non-guessable; plans without expires_at never expire
+CREATE TABLE shared_plans (
+    slug VARCHAR(16) PRIMARY KEY,
+    run_id UUID NOT NULL,
+    response JSONB NOT NULL,
+    availability_as_of TIMESTAMPTZ,
+    expires_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT NOW() NOT NULL
+);
+
+CREATE INDEX idx_shared_plans_expires_at ON shared_plans(expires_at);
@@ -0,0 +1,88 @@
+-- Give each country its own service catalog
+-- A service ID (matching the provider identifiers) now has one row per country it is sold in
+ALTER TABLE service_plans DROP CONSTRAINT service_plans_service_id_fkey;
+ALTER TABLE service_addons DROP CONSTRAINT service_addons_parent_service_id_fkey;
+ALTER TABLE service_addons DROP CONSTRAINT service_addons_parent_service_id_channel_id_key;
+
+ALTER TABLE streaming_services DROP CONSTRAINT streaming_services_pkey;
+ALTER TABLE streaming_services ADD PRIMARY KEY (id, country);
+
+-- Plans, add-ons and bundles are sold in a single country
+ALTER TABLE service_plans ADD COLUMN country VARCHAR(2) DEFAULT 'US' NOT NULL;
+ALTER TABLE service_plans
+    ADD FOREIGN KEY (service_id, country) REFERENCES streaming_services(id, country);
+
+ALTER TABLE service_addons ADD COLUMN country VARCHAR(2) DEFAULT 'US' NOT NULL;
+ALTER TABLE service_addons
+    ADD FOREIGN KEY (parent_service_id, country) REFERENCES streaming_services(id, country);
+ALTER TABLE service_addons ADD UNIQUE (parent_service_id, channel_id, country);
+
+ALTER TABLE service_bundles ADD COLUMN country VARCHAR(2) DEFAULT 'US' NOT NULL;
+
+CREATE INDEX idx_streaming_services_country ON streaming_services(country);
+
+-- Seed Canadian services (CAD) and plans with current pricing (as of December 2025)
+INSERT INTO streaming_services (id, name, base_monthly_cost, country) VALUES
+    ('netflix', 'Netflix', 18.99, 'CA'),
+    ('prime', 'Amazon Prime Video', 12.98, 'CA'),
+    ('disney', 'Disney+', 12.99, 'CA'),
+    ('apple', 'Apple TV+', 12.99, 'CA'),
+    ('paramount', 'Paramount+', 10.99, 'CA'),
+    ('crave', 'Crave', 22.00, 'CA')
+ON CONFLICT (id, country) DO NOTHING;
+
+INSERT INTO service_plans (id, service_id, name, monthly_cost, has_ads, max_quality, country) VALUES
+    ('netflix_ads_ca', 'netflix', 'Standard with ads', 7.99, true, 'HD', 'CA'),
+    ('netflix_standard_ca', 'netflix', 'Standard', 18.99, false, 'HD', 'CA'),
+    ('netflix_premium_ca', 'netflix', 'Premium', 23.99, false, '4K', 'CA'),
+    ('prime_ads_ca', 'prime', 'Prime Video (With Ads)', 9.99, true, '4K', 'CA'),
+    ('prime_ad_free_ca', 'prime', 'Prime Video (Ad-Free)', 12.98, false, '4K', 'CA'),
+    ('disney_standard_ads_ca', 'disney', 'Standard with Ads', 8.99, true, 'HD', 'CA'),
+    ('disney_standard_ca', 'disney', 'Standard', 12.99, false, 'HD', 'CA'),
+    ('disney_premium_ca', 'disney', 'Premium', 15.99, false, '4K', 'CA'),
+    ('apple_ca', 'apple', 'Apple TV+', 12.99, false, '4K', 'CA'),
+    ('paramount_basic_ca', 'paramount', 'Basic (With Ads)', 6.99, true, 'HD', 'CA'),
+    ('paramount_premium_ca', 'paramount', 'Premium', 10.99, false, '4K', 'CA'),
+    ('crave_basic_ca', 'crave', 'Basic with Ads', 9.99, true, 'HD', 'CA'),
+    ('crave_premium_ca', 'crave', 'Premium Ad-Free', 22.00, false, '4K', 'CA')
+ON CONFLICT (id) DO NOTHING;
+
+INSERT INTO service_addons (id, parent_service_id, channel_id, name, monthly_cost, country) VALUES
+    ('prime_starz_ca', 'prime', 'starz', 'STARZ (Prime Video Channel)', 5.99, 'CA'),
+    ('prime_britbox_ca', 'prime', 'britbox', 'BritBox (Prime Video Channel)', 8.99, 'CA')
+ON CONFLICT (id) DO NOTHING;
+
+-- Seed United Kingdom services (GBP) and plans with current pricing (as of December 2025)
+INSERT INTO streaming_services (id, name, base_monthly_cost, country) VALUES
+    ('netflix', 'Netflix', 12.99, 'GB'),
+    ('prime', 'Amazon Prime Video', 11.98, 'GB'),
+    ('disney', 'Disney+', 9.99, 'GB'),
+    ('apple', 'Apple TV+', 9.99, 'GB'),
+    ('paramount', 'Paramount+', 7.99, 'GB')
+ON CONFLICT (id, country) DO NOTHING;
+
+INSERT INTO service_plans (id, service_id, name, monthly_cost, has_ads, max_quality, country) VALUES
+    ('netflix_ads_gb', 'netflix', 'Standard with adverts', 5.99, true, 'HD', 'GB'),
+    ('netflix_standard_gb', 'netflix', 'Standard', 12.99, false, 'HD', 'GB'),
+    ('netflix_premium_gb', 'netflix', 'Premium', 18.99, false, '4K', 'GB'),
+    ('prime_ads_gb', 'prime', 'Prime Video (With Ads)', 8.99, true, '4K', 'GB'),
+    ('prime_ad_free_gb', 'prime', 'Prime Video (Ad-Free)', 11.98, false, '4K', 'GB'),
+    ('disney_standard_ads_gb', 'disney', 'Standard with Ads', 5.99, true, 'HD', 'GB'),
+    ('disney_standard_gb', 'disney', 'Standard', 9.99, false, 'HD', 'GB'),
+    ('disney_premium_gb', 'disney', 'Premium', 14.99, false, '4K', 'GB'),
+    ('apple_gb', 'apple', 'Apple TV+', 9.99, false, '4K', 'GB'),
+    ('paramount_basic_gb', 'paramount', 'Basic (With Ads)', 4.99, true, 'HD', 'GB'),
+    ('paramount_standard_gb', 'paramount', 'Standard', 7.99, false, 'HD', 'GB'),
+    ('paramount_premium_gb', 'paramount', 'Premium', 10.99, false, '4K', 'GB')
+ON CONFLICT (id) DO NOTHING;
+
+INSERT INTO service_addons (id, parent_service_id, channel_id, name, monthly_cost, country) VALUES
+    ('prime_britbox_gb', 'prime', 'britbox', 'BritBox (Prime Video Channel)', 5.99, 'GB'),
+    ('prime_mgmplus_gb', 'prime', 'mgmplus', 'MGM+ (Prime Video Channel)', 3.99, 'GB')
+ON CONFLICT (id) DO NOTHING;
+
+-- Watchmode source IDs are the same in every region
+UPDATE streaming_services s
+SET watchmode_service_id = us.watchmode_service_id
+FROM streaming_services us
+WHERE us.id = s.id AND us.country = 'US' AND s.country <> 'US';
@@ -0,0 +1,23 @@
+-- Record the currency each service is priced in (ISO 4217)
+-- Plans, add-ons and bundles are priced in the currency of their service's country
+ALTER TABLE streaming_services ADD COLUMN currency VARCHAR(3) DEFAULT 'USD' NOT NULL;
+
+UPDATE streaming_services SET currency = 'CAD' WHERE country = 'CA';
+UPDATE streaming_services SET currency = 'GBP' WHERE country = 'GB';
+
+-- Create exchange rates table (maintained locally, used to normalize costs to a
+-- reporting currency)
+-- units_per_usd is how many units of the currency one US dollar buys
+CREATE TABLE exchange_rates (
+    currency VARCHAR(3) PRIMARY KEY,
+    units_per_usd DECIMAL(18, 8) NOT NULL CHECK (units_per_usd > 0),
+    updated_at TIMESTAMPTZ DEFAULT NOW() NOT NULL
+);
+
+-- Seed exchange rates (as of December 2025)
+INSERT INTO exchange_rates (currency, units_per_usd) VALUES
+    ('USD', 1.00000000),
+    ('CAD', 1.38000000),
+    ('GBP', 0.75000000),
+    ('EUR', 0.86000000)
+ON CONFLICT (currency) DO NOTHING;
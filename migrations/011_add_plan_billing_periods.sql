@@ -0,0 +1,24 @@
+-- Model billing periods and minimum commitments per plan
+-- A plan now charges `price` every `billing_period_months` months, and must be paid for
+-- at least `commitment_months` months once taken (month-to-month plans have 1 and 1)
+ALTER TABLE service_plans RENAME COLUMN monthly_cost TO price;
+
+ALTER TABLE service_plans
+    ADD COLUMN billing_period_months INTEGER DEFAULT 1 NOT NULL CHECK (billing_period_months > 0);
+ALTER TABLE service_plans
+    ADD COLUMN commitment_months INTEGER DEFAULT 1 NOT NULL;
+ALTER TABLE service_plans
+    ADD CONSTRAINT service_plans_commitment_covers_billing_period
+    CHECK (commitment_months >= billing_period_months);
+
+-- Seed US annual plans with current pricing (as of December 2025)
+INSERT INTO service_plans (
+    id, service_id, name, price, has_ads, max_quality, billing_period_months, commitment_months
+) VALUES
+    ('disney_premium_annual', 'disney', 'Premium (No Ads), billed annually', 189.99, false, '4K', 12, 12),
+    ('hbo_standard_annual', 'hbo', 'Standard, billed annually', 184.99, false, 'HD', 12, 12),
+    ('paramount_essential_annual', 'paramount', 'Essential (With Ads), billed annually', 59.99, true, 'HD', 12, 12),
+    ('paramount_premium_annual', 'paramount', 'Premium, billed annually', 119.99, false, '4K', 12, 12),
+    ('peacock_premium_annual', 'peacock', 'Premium (With Ads), billed annually', 109.99, true, 'HD', 12, 12),
+    ('peacock_premium_plus_annual', 'peacock', 'Premium Plus, billed annually', 169.99, false, '4K', 12, 12)
+ON CONFLICT (id) DO NOTHING;
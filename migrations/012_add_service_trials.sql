@@ -0,0 +1,9 @@
+-- Record the free trial each service offers new subscribers
+-- trial_days is NULL when the service offers no trial
+ALTER TABLE streaming_services ADD COLUMN trial_days INTEGER CHECK (trial_days > 0);
+ALTER TABLE streaming_services
+    ADD COLUMN trial_new_customers_only BOOLEAN DEFAULT true NOT NULL;
+
+-- Seed trial lengths (as of December 2025). Week-long trials (apple, paramount, starz,
+-- crave) are left out, as the rotation planner only uses trials covering a whole month
+UPDATE streaming_services SET trial_days = 30 WHERE id IN ('hulu', 'prime');
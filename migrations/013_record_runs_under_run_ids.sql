@@ -0,0 +1,23 @@
+-- Record optimization runs under a server-generated run ID instead of the client-supplied
+-- x-request-id header, so a run can no longer be shadowed by another one sent under the same ID
+-- Older runs sharing an ID get a fresh one; the latest keeps it, as lookups returned it so far
+UPDATE optimization_requests
+SET request_id = gen_random_uuid()
+WHERE id IN (
+    SELECT id
+    FROM (
+        SELECT id, ROW_NUMBER() OVER (PARTITION BY request_id ORDER BY created_at DESC) AS position
+        FROM optimization_requests
+    ) ranked
+    WHERE position > 1
+);
+
+ALTER TABLE optimization_requests RENAME COLUMN request_id TO run_id;
+DROP INDEX idx_optimization_requests_request_id;
+ALTER TABLE optimization_requests
+    ADD CONSTRAINT optimization_requests_run_id_key UNIQUE (run_id);
+
+-- Every shared plan is a snapshot of a recorded run
+ALTER TABLE shared_plans
+    ADD CONSTRAINT shared_plans_run_id_fkey
+    FOREIGN KEY (run_id) REFERENCES optimization_requests(run_id);
@@ -5,19 +5,27 @@ use tokio::sync::mpsc;
 
 use crate::error::AppError;
 use crate::error::AppResult;
+use crate::models::Country;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CacheKey {
-    TitleSearch(String),
-    Availability(String),
+    TitleSearch(Country, String),
+    Availability(Country, String),
     ImdbToWatchmode(String),
 }
 
 impl Display for CacheKey {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            CacheKey::TitleSearch(query) => write!(f, "search:{}", query.to_lowercase()),
-            CacheKey::Availability(id) => write!(f, "avail:{}", id),
+            CacheKey::TitleSearch(country, query) => write!(
+                f,
+                "search:{}:{}",
+                country.code().to_lowercase(),
+                query.to_lowercase()
+            ),
+            CacheKey::Availability(country, id) => {
+                write!(f, "avail:{}:{}", country.code().to_lowercase(), id)
+            }
             CacheKey::ImdbToWatchmode(imdb_id) => write!(f, "imdb2wm:{}", imdb_id),
         }
     }
@@ -195,26 +203,32 @@ mod tests {
 
     #[test]
     fn test_cache_key_display_title_search() {
-        let key = CacheKey::TitleSearch("Inception".to_string());
-        assert_eq!(format!("{}", key), "search:inception");
+        let key = CacheKey::TitleSearch(Country::Us, "Inception".to_string());
+        assert_eq!(format!("{}", key), "search:us:inception");
     }
 
     #[test]
     fn test_cache_key_display_title_search_lowercase() {
-        let key = CacheKey::TitleSearch("THE MATRIX".to_string());
-        assert_eq!(format!("{}", key), "search:the matrix");
+        let key = CacheKey::TitleSearch(Country::Us, "THE MATRIX".to_string());
+        assert_eq!(format!("{}", key), "search:us:the matrix");
     }
 
     #[test]
     fn test_cache_key_display_availability() {
-        let key = CacheKey::Availability("tt1375666".to_string());
-        assert_eq!(format!("{}", key), "avail:tt1375666");
+        let key = CacheKey::Availability(Country::Us, "tt1375666".to_string());
+        assert_eq!(format!("{}", key), "avail:us:tt1375666");
     }
 
     #[test]
     fn test_cache_key_display_availability_watchmode() {
-        let key = CacheKey::Availability("3173903".to_string());
-        assert_eq!(format!("{}", key), "avail:3173903");
+        let key = CacheKey::Availability(Country::Us, "3173903".to_string());
+        assert_eq!(format!("{}", key), "avail:us:3173903");
+    }
+
+    #[test]
+    fn test_cache_key_display_availability_per_country() {
+        let key = CacheKey::Availability(Country::Gb, "tt1375666".to_string());
+        assert_eq!(format!("{}", key), "avail:gb:tt1375666");
     }
 
     #[test]
@@ -231,7 +245,7 @@ mod tests {
         let client = create_redis_client(&redis_url).unwrap();
         let (cache, _handle) = Cache::new(client).await;
 
-        let key = CacheKey::TitleSearch("nonexistent_key_12345".to_string());
+        let key = CacheKey::TitleSearch(Country::Us, "nonexistent_key_12345".to_string());
         let retrieved: Option<Vec<String>> = cache.get_from_cache(&key).await.unwrap();
 
         assert_eq!(retrieved, None);
@@ -245,7 +259,7 @@ mod tests {
         let client = create_redis_client(&redis_url).unwrap();
         let (cache, _handle) = Cache::new(client.clone()).await;
 
-        let key = CacheKey::TitleSearch("test_async_write".to_string());
+        let key = CacheKey::TitleSearch(Country::Us, "test_async_write".to_string());
         let value = vec!["item1".to_string(), "item2".to_string()];
 
         // Write using async method (non-blocking)
@@ -274,15 +288,15 @@ mod tests {
         // Write multiple values asynchronously
         let keys_values = vec![
             (
-                CacheKey::TitleSearch("async_test_1".to_string()),
+                CacheKey::TitleSearch(Country::Us, "async_test_1".to_string()),
                 vec!["a".to_string()],
             ),
             (
-                CacheKey::TitleSearch("async_test_2".to_string()),
+                CacheKey::TitleSearch(Country::Us, "async_test_2".to_string()),
                 vec!["b".to_string()],
             ),
             (
-                CacheKey::TitleSearch("async_test_3".to_string()),
+                CacheKey::TitleSearch(Country::Us, "async_test_3".to_string()),
                 vec!["c".to_string()],
             ),
         ];
@@ -315,7 +329,7 @@ mod tests {
         let client = create_redis_client(&redis_url).unwrap();
         let (cache, handle) = Cache::new(client.clone()).await;
 
-        let key = CacheKey::TitleSearch("test_shutdown".to_string());
+        let key = CacheKey::TitleSearch(Country::Us, "test_shutdown".to_string());
         let value = vec!["shutdown_test".to_string()];
 
         // Write using async method
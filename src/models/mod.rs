@@ -1,6 +1,8 @@
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, fmt::Display};
+use uuid::Uuid;
 
 /// Identifier for a title, which can be either IMDB ID or provider-specific ID
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -80,40 +82,602 @@ impl From<ApiShow> for Title {
     }
 }
 
-/// Represents a streaming service
+/// Represents a streaming service subscribed to on a specific plan
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StreamingService {
     pub id: String,
     pub name: String,
-    pub monthly_cost: f64,
+    /// Standalone plan price per month (the effective monthly cost over the horizon for
+    /// plans billed less often), or 0 when the service is paid for through a bundle
+    pub monthly_cost: Decimal,
+    /// Currency `monthly_cost` is quoted in (ISO 4217)
+    pub currency: String,
+    /// Chosen plan (pricing tier) for this service
+    pub plan_id: String,
+    pub plan_name: String,
+    pub has_ads: bool,
+    /// Months covered by each payment (12 for annual plans)
+    pub billing_period_months: u32,
+    /// Minimum months the plan must be paid for once taken
+    pub commitment_months: u32,
+    /// Bundle the service is bought through, or `None` when bought standalone
+    pub bundle_id: Option<String>,
+    /// Parent service an add-on channel is bought through, or `None` for regular services
+    pub parent_service_id: Option<String>,
+}
+
+/// A bundle of several streaming services sold at a single price
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceBundle {
+    pub id: String,
+    pub name: String,
+    pub monthly_cost: Decimal,
+    /// Currency `monthly_cost` is quoted in (ISO 4217)
+    pub currency: String,
+    /// IDs of the services included in the bundle
+    pub service_ids: Vec<String>,
+}
+
+/// Which plan tiers the optimizer may choose from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdPreference {
+    /// Only ad-free plans are considered
+    #[default]
+    AdFreeOnly,
+    /// Ad-supported plans are considered alongside ad-free ones
+    AdsOk,
+}
+
+/// Country whose catalog, availability and pricing are used (ISO 3166-1 alpha-2)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Country {
+    #[default]
+    #[serde(alias = "us")]
+    Us,
+    #[serde(alias = "ca")]
+    Ca,
+    /// United Kingdom
+    #[serde(alias = "gb")]
+    Gb,
+}
+
+impl Country {
+    /// Uppercase country code, as stored in the database
+    pub fn code(self) -> &'static str {
+        match self {
+            Country::Us => "US",
+            Country::Ca => "CA",
+            Country::Gb => "GB",
+        }
+    }
+}
+
+impl std::fmt::Display for Country {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// Solver backend used to find a configuration
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SolverBackend {
+    /// Exact integer programming model, falling back to `Greedy` past the time limit
+    #[default]
+    Milp,
+    /// Fast weighted set-cover heuristic for large catalogs, not proven optimal
+    Greedy,
+    /// Checks every selection; only for tiny catalogs
+    Exhaustive,
 }
 
 /// Request to find optimal streaming services
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct OptimizationRequest {
+    #[serde(default)]
     pub must_have: Vec<TitleId>,
+    #[serde(default)]
     pub nice_to_have: Vec<TitleId>,
+    /// Optional priority (1-10) per title, keyed by title ID (e.g. "tt1375666").
+    /// Titles without an entry default to priority 1
+    #[serde(default)]
+    pub priorities: HashMap<String, u8>,
+    /// Whether ad-supported plans may be chosen (ad-free only by default)
+    #[serde(default)]
+    pub ads: AdPreference,
+    /// Country to optimize for (US if omitted)
+    #[serde(default)]
+    pub country: Country,
+    /// Currency to also report each configuration's total cost in (ISO 4217, e.g. "USD"),
+    /// converted with the locally maintained exchange rates
+    #[serde(default)]
+    pub reporting_currency: Option<String>,
+    /// Months to price the configurations over. When set, annual and other committed
+    /// plans are considered too, and every plan is priced at its effective monthly cost
+    /// over the horizon. Only month-to-month plans are considered if omitted
+    #[serde(default)]
+    pub horizon_months: Option<u32>,
+    /// Maximum number of Pareto frontier configurations to return, cheapest first.
+    /// The full frontier is returned if omitted
+    #[serde(default)]
+    pub frontier_limit: Option<usize>,
+    /// Return up to this many of the cheapest distinct service sets covering all
+    /// must-haves instead of the Pareto frontier
+    #[serde(default)]
+    pub max_configurations: Option<usize>,
+    /// Monthly budget cap. When set, total cost becomes a hard constraint and
+    /// weighted coverage of must-have and nice-to-have titles is maximized instead
+    #[serde(default)]
+    pub budget: Option<Decimal>,
+    /// When set, titles may also be covered by a one-off rental or purchase at its price
+    #[serde(default)]
+    pub allow_rent_or_buy: bool,
+    /// When set, free sources no longer count as covering a title
+    #[serde(default)]
+    pub exclude_free: bool,
+    /// Services the user already holds. When given, each configuration also lists the
+    /// services to keep, cancel and add
+    #[serde(default)]
+    pub current_services: Vec<CurrentService>,
+    /// Service or add-on IDs every configuration must include
+    #[serde(default)]
+    pub include_services: Vec<String>,
+    /// Service or add-on IDs no configuration may include
+    #[serde(default)]
+    pub exclude_services: Vec<String>,
+    /// Maximum number of services and add-on channels in any configuration
+    #[serde(default)]
+    pub max_services: Option<usize>,
+    /// Extra monthly cost worth paying per service avoided; a configuration with fewer
+    /// services is preferred when it costs at most this much more per service saved
+    #[serde(default)]
+    pub simplicity_tolerance: Option<Decimal>,
+    /// Solver backend for the configurations (integer programming by default; budget
+    /// mode and rotation plans always use it, though budget mode falls back to the greedy
+    /// heuristic past the time limit)
+    #[serde(default)]
+    pub solver: SolverBackend,
+    /// Time the solver may spend on the request in milliseconds (10 seconds if omitted)
+    #[serde(default)]
+    pub time_limit_ms: Option<u64>,
+    /// When set, each configuration also reports what every selected service is worth
+    #[serde(default)]
+    pub explain: bool,
+    /// When set, the response reports the price at which each service would change the
+    /// optimal configuration (not supported in budget mode)
+    #[serde(default)]
+    pub price_sensitivity: bool,
+}
+
+/// A service the user already holds
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CurrentService {
+    pub service_id: String,
+    /// What the user pays for it today, e.g. 0 for a phone-plan perk.
+    /// Defaults to the service's cheapest eligible plan
+    #[serde(default)]
+    pub monthly_cost: Option<Decimal>,
+    /// Whether the current plan shows ads (only used with `monthly_cost`)
+    #[serde(default)]
+    pub has_ads: bool,
+    /// Months between charges of the current plan (only used with `monthly_cost`,
+    /// defaults to 1)
+    #[serde(default)]
+    pub billing_period_months: Option<u32>,
+    /// Minimum months the current plan is committed for (only used with `monthly_cost`,
+    /// defaults to 1)
+    #[serde(default)]
+    pub commitment_months: Option<u32>,
 }
 
 /// Response with ordered list of streaming service configurations
 #[derive(Debug, Serialize)]
 pub struct OptimizationResponse {
-    /// Ordered list of service configurations from most preferred to least preferred
+    /// Cost/coverage Pareto frontier ordered from cost-optimal to coverage-optimal
     /// First configuration is the optimal (cost-focused) solution
-    /// Subsequent configurations trade cost for better nice-to-have coverage
+    /// Each subsequent configuration costs more and covers strictly more nice-to-haves
     pub configurations: Vec<ServiceConfiguration>,
+    /// Country the configurations were optimized for
+    pub country: Country,
+    /// Currency all prices are quoted in (ISO 4217)
+    pub currency: String,
+    /// Currency `normalized_total_cost` is reported in (only when `reporting_currency`
+    /// is set)
+    pub reporting_currency: Option<String>,
+    /// Months plans were priced over (only when `horizon_months` is set)
+    pub horizon_months: Option<u32>,
     /// Titles that are unavailable on any streaming service
     pub unavailable_must_have: Vec<TitleId>,
     pub unavailable_nice_to_have: Vec<TitleId>,
+    /// How robust the optimal configuration is to price changes (only when
+    /// `price_sensitivity` is set)
+    pub price_sensitivity: Option<Vec<PriceSensitivity>>,
+    /// When the oldest availability data behind the result was fetched from the provider
+    pub availability_as_of: Option<DateTime<Utc>>,
+    /// ID the run is recorded under, for reloading and sharing it (only set by `/optimize`)
+    pub run_id: Option<Uuid>,
+}
+
+/// A recorded optimization run, reloadable by its run ID
+#[derive(Debug, Clone, Serialize)]
+pub struct OptimizationRun {
+    pub run_id: Uuid,
+    pub must_have_titles: Vec<String>,
+    pub nice_to_have_titles: Vec<String>,
+    /// Percentage of availability lookups served from the cache
+    pub cache_hit_rate: Option<f64>,
+    /// Availability lookups that went to the streaming provider
+    pub api_calls_made: i32,
+    /// Cost of the optimal configuration
+    pub total_cost: Option<Decimal>,
+    /// Services of the optimal configuration
+    pub services_selected: Option<Vec<String>>,
+    pub processing_time_ms: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    /// The response as it was returned (`None` when the run failed)
+    pub response: Option<serde_json::Value>,
+    /// Why the run failed (`None` when it succeeded)
+    pub error: Option<String>,
+}
+
+/// Request to share the result of a recorded optimization run
+#[derive(Debug, Clone, Deserialize)]
+pub struct SharePlanRequest {
+    /// Run ID of the run to share
+    pub run_id: Uuid,
+    /// Hours until the link stops working. The link never expires if omitted
+    #[serde(default)]
+    pub expires_in_hours: Option<u32>,
+}
+
+/// Short link to a shared plan
+#[derive(Debug, Clone, Serialize)]
+pub struct SharedPlanLink {
+    pub slug: String,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// A shared snapshot of an optimization result
+#[derive(Debug, Clone, Serialize)]
+pub struct SharedPlan {
+    pub slug: String,
+    /// The optimization response as it was returned
+    pub response: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    /// When the oldest availability data behind the plan was fetched from the provider
+    pub availability_as_of: Option<DateTime<Utc>>,
+    /// Set when the availability data is old enough that the plan may be out of date
+    pub warning: Option<String>,
+}
+
+/// Price at which a single service would change the optimal configuration, assuming no
+/// other price changes
+#[derive(Debug, Clone, Serialize)]
+pub struct PriceSensitivity {
+    pub service_id: String,
+    pub service_name: String,
+    /// Whether the service is part of the optimal configuration
+    pub selected: bool,
+    /// Monthly price today: the chosen plan, add-on or bundle price for selected services,
+    /// the cheapest eligible plan for others
+    pub current_price: Decimal,
+    /// Price above which the plan, add-on or bundle a selected service is held through is
+    /// replaced (by another plan of the service, another bundle, or other services), or
+    /// below which another service is picked up through its cheapest plan. `None` when
+    /// no price change alters the configuration
+    pub threshold_price: Option<Decimal>,
+    /// Currency both prices are quoted in (ISO 4217)
+    pub currency: String,
+    /// Services of the configuration that takes over past the threshold
+    pub alternative_services: Vec<String>,
 }
 
 /// A single streaming service configuration with coverage and cost information
 #[derive(Debug, Serialize, Clone)]
 pub struct ServiceConfiguration {
     pub services: Vec<StreamingService>,
-    pub total_cost: f64,
+    /// Bundles bought; their member services are listed in `services` with a `bundle_id`
+    pub bundles: Vec<ServiceBundle>,
+    pub total_cost: Decimal,
+    /// Currency every price in the configuration is quoted in (ISO 4217)
+    pub currency: String,
+    /// `total_cost` converted to the requested reporting currency, rounded to cents
+    /// (only when `reporting_currency` is set)
+    pub normalized_total_cost: Option<Decimal>,
     pub must_have_coverage: usize,
     pub nice_to_have_coverage: usize,
+    /// Nice-to-have coverage where each covered title counts for its priority
+    pub nice_to_have_weighted_coverage: f64,
+    /// Must-have titles left uncovered to stay within the budget (budget mode only)
+    pub dropped_must_have: Vec<TitleId>,
+    /// Route chosen for each covered title, must-haves first
+    pub title_routes: Vec<TitleRoute>,
+    /// Available nice-to-have titles this configuration leaves uncovered
+    pub uncovered_nice_to_have: Vec<TitleId>,
+    /// Combined price of one-off rentals and purchases, included in `total_cost`
+    pub one_off_cost: Decimal,
+    /// Longest minimum commitment among the selected plans, in months (1 when every
+    /// plan is month-to-month)
+    pub commitment_months: u32,
+    /// Changes from the user's current services (only when current services are given)
+    pub changes: Option<ServiceChanges>,
+    /// What each selected service contributes (only when `explain` is set)
+    pub service_analysis: Option<Vec<ServiceAnalysis>>,
+    /// Backend that produced the configuration
+    pub solver: SolverBackend,
+    /// Whether the backend proved the configuration optimal
+    pub proven_optimal: bool,
+}
+
+/// What a selected service contributes to a configuration, and what dropping it changes
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceAnalysis {
+    pub service_id: String,
+    pub service_name: String,
+    /// Monthly cost of holding the service: its plan or add-on price, or the price of
+    /// the bundle it is held through
+    pub monthly_cost: Decimal,
+    /// Covered titles no other selected service or one-off covers
+    pub unique_titles: Vec<TitleId>,
+    /// Cost of the re-optimized configuration without this service. `None` when no
+    /// configuration covers every must-have without it
+    pub cost_without: Option<Decimal>,
+    /// `monthly_cost` divided by the number of unique titles, rounded to cents (`None`
+    /// when there are none)
+    pub cost_per_unique_title: Option<Decimal>,
+    /// Currency every cost above is quoted in (ISO 4217)
+    pub currency: String,
+}
+
+/// Incremental recommendation from the user's current services to a configuration
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceChanges {
+    /// Current services that are part of the configuration
+    pub keep: Vec<String>,
+    /// Current services that are not part of the configuration
+    pub cancel: Vec<String>,
+    /// Services and add-ons in the configuration the user does not hold yet
+    pub add: Vec<String>,
+    /// What the user pays for their current services today
+    pub current_cost: Decimal,
+    /// Monthly subscription cost of the configuration minus `current_cost`.
+    /// One-off rentals and purchases are left out
+    pub net_monthly_change: Decimal,
+    /// Currency both costs are quoted in (ISO 4217)
+    pub currency: String,
+}
+
+/// How a title is covered in a configuration
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum CoverageRoute {
+    Subscription,
+    /// Watched for free (usually with ads), nothing to pay
+    Free,
+    Rent,
+    Buy,
+}
+
+/// The route chosen to cover a single title
+#[derive(Debug, Clone, Serialize)]
+pub struct TitleRoute {
+    pub title: TitleId,
+    pub route: CoverageRoute,
+    /// Service the title is watched, rented or bought on
+    pub service_id: String,
+    pub service_name: String,
+    /// One-off price for rentals and purchases; subscriptions are priced per service
+    pub price: Option<Decimal>,
+    /// Currency `price` is quoted in (ISO 4217), set along with it
+    pub currency: Option<String>,
+    /// Every selected service or add-on channel carrying the title
+    pub covering_services: Vec<String>,
+    /// Link to the title on the chosen service, from the provider's availability data
+    pub link: Option<String>,
+    /// Video quality on the chosen service (e.g. "hd", "uhd")
+    pub quality: Option<String>,
+}
+
+/// Request to compare the optimal configuration before and after editing a watchlist
+#[derive(Debug, Deserialize)]
+pub struct OptimizationDiffRequest {
+    /// Request the changes are applied to
+    #[serde(flatten)]
+    pub base: OptimizationRequest,
+    /// Titles to add as must-haves (moved there if already a nice-to-have)
+    #[serde(default)]
+    pub add_must_have: Vec<TitleId>,
+    /// Titles to add as nice-to-haves (moved there if already a must-have)
+    #[serde(default)]
+    pub add_nice_to_have: Vec<TitleId>,
+    /// Titles to remove from either list
+    #[serde(default)]
+    pub remove: Vec<TitleId>,
+}
+
+/// Change in the optimal configuration caused by editing a watchlist
+#[derive(Debug, Serialize)]
+pub struct OptimizationDiffResponse {
+    /// Optimal configuration of the edited request (`None` when it has none)
+    pub configuration: Option<ServiceConfiguration>,
+    /// Total cost of the edited optimum minus that of the base optimum
+    pub cost_change: Decimal,
+    /// Currency `cost_change` is quoted in (ISO 4217)
+    pub currency: String,
+    /// Currency `normalized_cost_change` is reported in (only when `reporting_currency`
+    /// is set)
+    pub reporting_currency: Option<String>,
+    /// `cost_change` converted to the reporting currency (only when `reporting_currency`
+    /// is set)
+    pub normalized_cost_change: Option<Decimal>,
+    /// Service IDs selected after the edit but not before
+    pub services_added: Vec<String>,
+    /// Service IDs selected before the edit but not after
+    pub services_dropped: Vec<String>,
+    pub must_have_coverage_change: i64,
+    pub nice_to_have_coverage_change: i64,
+    /// Titles of the edited request that are unavailable on any streaming service
+    pub unavailable_must_have: Vec<TitleId>,
+    pub unavailable_nice_to_have: Vec<TitleId>,
+}
+
+/// Request to plan a month-by-month subscription rotation
+#[derive(Debug, Deserialize)]
+pub struct RotationRequest {
+    /// Titles to schedule; every available must-have is watched in at least one month
+    #[serde(flatten)]
+    pub watchlist: OptimizationRequest,
+    /// Planning horizon in months
+    pub months: u32,
+    /// Maximum number of services held in any single month (unlimited if omitted)
+    #[serde(default)]
+    pub max_services_per_month: Option<usize>,
+    /// Service IDs the user has never subscribed to, and so can start a new-customer free
+    /// trial on
+    #[serde(default)]
+    pub never_subscribed: Vec<String>,
+}
+
+/// Response with a month-by-month subscription schedule
+#[derive(Debug, Serialize)]
+pub struct RotationResponse {
+    /// One entry per month of the planning horizon, in order
+    pub schedule: Vec<RotationMonth>,
+    /// Total spend over the whole horizon
+    pub total_cost: Decimal,
+    /// Currency every price in the schedule is quoted in (ISO 4217)
+    pub currency: String,
+    pub must_have_coverage: usize,
+    pub nice_to_have_coverage: usize,
+    /// Titles that are unavailable on any streaming service
+    pub unavailable_must_have: Vec<TitleId>,
+    pub unavailable_nice_to_have: Vec<TitleId>,
+    /// Free trials the user could start but the planner did not use, as they are too short
+    /// to cover a month of the schedule
+    pub skipped_trials: Vec<RotationSkippedTrial>,
+}
+
+/// A single month of a rotation schedule
+#[derive(Debug, Serialize, Clone)]
+pub struct RotationMonth {
+    /// 1-based month index within the horizon
+    pub month: u32,
+    /// Services held during this month
+    pub services: Vec<StreamingService>,
+    /// Bundles held during this month
+    pub bundles: Vec<ServiceBundle>,
+    /// Service IDs to subscribe to at the start of this month
+    pub subscribe: Vec<String>,
+    /// Service IDs to cancel before the next month renews (or before their trial converts)
+    pub cancel: Vec<String>,
+    /// Free trials started this month; their services are not in `services` or paid for
+    pub trials: Vec<RotationTrial>,
+    /// Titles scheduled to be watched this month
+    pub titles: Vec<TitleId>,
+    pub monthly_cost: Decimal,
+}
+
+/// Request to optimize subscriptions shared by a household
+#[derive(Debug, Deserialize)]
+pub struct HouseholdRequest {
+    /// Household members, each with their own watchlist
+    pub members: Vec<HouseholdMember>,
+    /// Options shared by the whole household (country, ads, budget, ...); titles and
+    /// priorities are given per member instead
+    #[serde(flatten)]
+    pub options: OptimizationRequest,
+    /// Split the cost of each configuration between members in proportion to usage
+    #[serde(default)]
+    pub split_cost: bool,
+}
+
+/// One member of a household and their watchlist
+#[derive(Debug, Clone, Deserialize)]
+pub struct HouseholdMember {
+    pub name: String,
+    #[serde(default)]
+    pub must_have: Vec<TitleId>,
+    #[serde(default)]
+    pub nice_to_have: Vec<TitleId>,
+    /// Priority from 1 to 10 per title ID; titles without an entry default to 1
+    #[serde(default)]
+    pub priorities: HashMap<String, u8>,
+}
+
+/// Response with configurations covering every member's must-haves
+#[derive(Debug, Serialize)]
+pub struct HouseholdResponse {
+    /// Cost/coverage Pareto frontier over the combined watchlists, as in
+    /// `OptimizationResponse`, with per-member coverage
+    pub configurations: Vec<HouseholdConfiguration>,
+    pub country: Country,
+    /// Currency all prices are quoted in (ISO 4217)
+    pub currency: String,
+    /// Currency `normalized_total_cost` is reported in (only when `reporting_currency`
+    /// is set)
+    pub reporting_currency: Option<String>,
+    /// Months plans were priced over (only when `horizon_months` is set)
+    pub horizon_months: Option<u32>,
+    /// Titles of any member that are unavailable on any streaming service
+    pub unavailable_must_have: Vec<TitleId>,
+    pub unavailable_nice_to_have: Vec<TitleId>,
+    /// When the oldest availability data behind the result was fetched from the provider
+    pub availability_as_of: Option<DateTime<Utc>>,
+}
+
+/// A configuration for the whole household and how it serves each member
+#[derive(Debug, Serialize)]
+pub struct HouseholdConfiguration {
+    #[serde(flatten)]
+    pub configuration: ServiceConfiguration,
+    /// One entry per member, in request order
+    pub members: Vec<MemberCoverage>,
+}
+
+/// How a household configuration covers one member's watchlist
+#[derive(Debug, Clone, Serialize)]
+pub struct MemberCoverage {
+    pub name: String,
+    pub must_have_coverage: usize,
+    pub nice_to_have_coverage: usize,
+    /// Nice-to-have coverage where each covered title counts for the member's priority
+    pub nice_to_have_weighted_coverage: f64,
+    /// Member's must-have titles left uncovered to stay within the budget (budget mode
+    /// only)
+    pub dropped_must_have: Vec<TitleId>,
+    /// Member's available nice-to-have titles the configuration leaves uncovered
+    pub uncovered_nice_to_have: Vec<TitleId>,
+    /// Member's share of `total_cost`, in proportion to the titles they watch on each
+    /// service (only when `split_cost` is set)
+    pub cost_share: Option<Decimal>,
+}
+
+/// A free trial started at the beginning of a rotation month
+#[derive(Debug, Serialize, Clone)]
+pub struct RotationTrial {
+    pub service_id: String,
+    pub service_name: String,
+    /// Length of the trial; titles scheduled on it must be watched within these days
+    pub trial_days: u32,
+    /// Plan the trial converts to after `trial_days` days unless cancelled: the plan held
+    /// the next month, or the cheapest eligible plan
+    pub converts_to_plan_id: String,
+    /// Price first charged when the trial converts
+    pub converts_to_monthly_cost: Decimal,
+    /// Whether the trial must be cancelled before it converts, as the schedule does not
+    /// hold the service the next month
+    pub cancel_before_conversion: bool,
+}
+
+/// A free trial left out of a rotation schedule
+#[derive(Debug, Serialize, Clone)]
+pub struct RotationSkippedTrial {
+    pub service_id: String,
+    pub service_name: String,
+    pub trial_days: u32,
 }
 
 /// Streaming availability data for a single title
@@ -130,6 +694,13 @@ pub struct ServiceAvailability {
     pub service_id: String,
     pub service_name: String,
     pub availability_type: AvailabilityType,
+    /// Add-on channel ID for `Addon` availability; `service_id` is then the parent service
+    #[serde(default)]
+    pub addon_id: Option<String>,
+    /// One-off price for `Rent` and `Buy` availability, in the currency of the country
+    /// the availability was fetched for
+    #[serde(default)]
+    pub price: Option<Decimal>,
     pub quality: Option<String>,
     pub link: Option<String>,
 }
@@ -160,6 +731,12 @@ pub struct ApiStreamingOption {
     pub service: ApiService,
     #[serde(rename = "type")]
     pub availability_type: String,
+    /// Add-on channel, present when `availability_type` is "addon"
+    #[serde(default)]
+    pub addon: Option<ApiAddon>,
+    /// Price, present for "rent" and "buy" options
+    #[serde(default)]
+    pub price: Option<ApiPrice>,
     #[serde(default)]
     pub quality: Option<String>,
     #[serde(default)]
@@ -172,6 +749,17 @@ pub struct ApiService {
     pub name: String,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiAddon {
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiPrice {
+    /// Decimal amount as a string (e.g. "3.99")
+    pub amount: String,
+}
+
 // ============================================================================
 // Watchmode API Types
 // ============================================================================
@@ -224,6 +812,9 @@ pub struct WatchmodeSource {
     #[serde(rename = "type")]
     pub source_type: String,
     pub format: Option<String>,
+    /// Price for rent and buy sources
+    #[serde(default)]
+    pub price: Option<Decimal>,
 }
 
 #[cfg(test)]
@@ -332,4 +923,25 @@ mod tests {
         assert_eq!(title.title_type, TitleType::Series);
         assert_eq!(title.release_year, Some(2021));
     }
+
+    #[test]
+    fn test_household_request_deserialize() {
+        let json = r#"{
+            "members": [
+                {"name": "Alice", "must_have": [{"Imdb": "tt1375666"}]},
+                {"name": "Bob", "nice_to_have": [{"Imdb": "tt0468569"}], "priorities": {"tt0468569": 5}}
+            ],
+            "country": "CA",
+            "split_cost": true
+        }"#;
+
+        let request: HouseholdRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.members.len(), 2);
+        assert_eq!(request.members[0].must_have.len(), 1);
+        assert!(request.members[0].nice_to_have.is_empty());
+        assert_eq!(request.members[1].priorities["tt0468569"], 5);
+        assert_eq!(request.options.country, Country::Ca);
+        assert!(request.options.must_have.is_empty());
+        assert!(request.split_cost);
+    }
 }
@@ -14,6 +14,7 @@ use crate::services::providers::StreamingProvider;
 use sqlx::PgPool;
 
 pub mod optimize;
+pub mod plans;
 pub mod recommendations;
 pub mod titles;
 
@@ -44,6 +45,12 @@ fn api_routes() -> Router<Arc<AppState>> {
     Router::new()
         .route("/titles/search", get(titles::search))
         .route("/optimize", post(optimize::optimize))
+        .route("/optimize/diff", post(optimize::diff))
+        .route("/optimize/household", post(optimize::household))
+        .route("/optimize/rotation", post(optimize::rotation))
+        .route("/optimize/runs/:run_id", get(optimize::run))
+        .route("/plans", post(plans::create))
+        .route("/plans/:slug", get(plans::get))
         .route("/recommendations", post(recommendations::recommend))
 }
 
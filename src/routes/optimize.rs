@@ -1,10 +1,18 @@
-use axum::{extract::State, Extension, Json};
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
 use std::sync::Arc;
+use uuid::Uuid;
 
 use crate::{
     error::AppResult,
     middleware::request_id::RequestId,
-    models::{OptimizationRequest, OptimizationResponse},
+    models::{
+        HouseholdRequest, HouseholdResponse, OptimizationDiffRequest, OptimizationDiffResponse,
+        OptimizationRequest, OptimizationResponse, OptimizationRun, RotationRequest,
+        RotationResponse,
+    },
     routes::AppState,
     services::optimization,
 };
@@ -19,6 +27,9 @@ pub async fn optimize(
         request_id = %request_id,
         must_have_count = request.must_have.len(),
         nice_to_have_count = request.nice_to_have.len(),
+        prioritized_count = request.priorities.len(),
+        ads = ?request.ads,
+        explain = request.explain,
         "Processing optimization request"
     );
 
@@ -29,10 +40,122 @@ pub async fn optimize(
     )
     .await?;
 
+    let optimal = response.configurations.first();
     tracing::info!(
         request_id = %request_id,
+        run_id = ?response.run_id,
+        nice_to_have_coverage = optimal.map(|c| c.nice_to_have_coverage),
+        nice_to_have_weighted_coverage = optimal.map(|c| c.nice_to_have_weighted_coverage),
         "Optimization completed"
     );
 
     Ok(Json(response))
 }
+
+/// Handler for what-if endpoint comparing a request before and after editing its titles
+pub async fn diff(
+    State(state): State<Arc<AppState>>,
+    Extension(request_id): Extension<RequestId>,
+    Json(request): Json<OptimizationDiffRequest>,
+) -> AppResult<Json<OptimizationDiffResponse>> {
+    tracing::info!(
+        request_id = %request_id,
+        must_have_count = request.base.must_have.len(),
+        nice_to_have_count = request.base.nice_to_have.len(),
+        add_must_have_count = request.add_must_have.len(),
+        add_nice_to_have_count = request.add_nice_to_have.len(),
+        remove_count = request.remove.len(),
+        "Processing optimization diff request"
+    );
+
+    let response = optimization::diff::optimize_diff(
+        state.db_pool.clone(),
+        state.streaming_provider.clone(),
+        request,
+    )
+    .await?;
+
+    tracing::info!(
+        request_id = %request_id,
+        cost_change = %response.cost_change,
+        "Optimization diff completed"
+    );
+
+    Ok(Json(response))
+}
+
+/// Handler for household endpoint optimizing over several members' watchlists
+pub async fn household(
+    State(state): State<Arc<AppState>>,
+    Extension(request_id): Extension<RequestId>,
+    Json(request): Json<HouseholdRequest>,
+) -> AppResult<Json<HouseholdResponse>> {
+    tracing::info!(
+        request_id = %request_id,
+        member_count = request.members.len(),
+        split_cost = request.split_cost,
+        "Processing household optimization request"
+    );
+
+    let response = optimization::household::optimize_household(
+        state.db_pool.clone(),
+        state.streaming_provider.clone(),
+        request,
+    )
+    .await?;
+
+    tracing::info!(
+        request_id = %request_id,
+        configurations = response.configurations.len(),
+        "Household optimization completed"
+    );
+
+    Ok(Json(response))
+}
+
+/// Handler for rotation planning endpoint
+pub async fn rotation(
+    State(state): State<Arc<AppState>>,
+    Extension(request_id): Extension<RequestId>,
+    Json(request): Json<RotationRequest>,
+) -> AppResult<Json<RotationResponse>> {
+    tracing::info!(
+        request_id = %request_id,
+        must_have_count = request.watchlist.must_have.len(),
+        nice_to_have_count = request.watchlist.nice_to_have.len(),
+        months = request.months,
+        "Processing rotation request"
+    );
+
+    let response = optimization::rotation::plan_rotation(
+        state.db_pool.clone(),
+        state.streaming_provider.clone(),
+        request,
+    )
+    .await?;
+
+    tracing::info!(
+        request_id = %request_id,
+        total_cost = %response.total_cost,
+        "Rotation planning completed"
+    );
+
+    Ok(Json(response))
+}
+
+/// Handler for reloading a past optimization run by its run ID
+pub async fn run(
+    State(state): State<Arc<AppState>>,
+    Extension(request_id): Extension<RequestId>,
+    Path(run_id): Path<Uuid>,
+) -> AppResult<Json<OptimizationRun>> {
+    tracing::info!(
+        request_id = %request_id,
+        run_id = %run_id,
+        "Fetching optimization run"
+    );
+
+    let run = optimization::runs::fetch_run(&state.db_pool, run_id).await?;
+
+    Ok(Json(run))
+}
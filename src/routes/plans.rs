@@ -0,0 +1,54 @@
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use std::sync::Arc;
+
+use crate::{
+    error::AppResult,
+    middleware::request_id::RequestId,
+    models::{SharePlanRequest, SharedPlan, SharedPlanLink},
+    routes::AppState,
+    services::plans,
+};
+
+/// Handler for sharing a recorded optimization run under a short link
+pub async fn create(
+    State(state): State<Arc<AppState>>,
+    Extension(request_id): Extension<RequestId>,
+    Json(request): Json<SharePlanRequest>,
+) -> AppResult<Json<SharedPlanLink>> {
+    tracing::info!(
+        request_id = %request_id,
+        run_id = %request.run_id,
+        expires_in_hours = request.expires_in_hours,
+        "Sharing optimization plan"
+    );
+
+    let link = plans::create_plan(&state.db_pool, request).await?;
+
+    tracing::info!(
+        request_id = %request_id,
+        slug = %link.slug,
+        "Shared optimization plan"
+    );
+
+    Ok(Json(link))
+}
+
+/// Handler for retrieving a shared plan by slug
+pub async fn get(
+    State(state): State<Arc<AppState>>,
+    Extension(request_id): Extension<RequestId>,
+    Path(slug): Path<String>,
+) -> AppResult<Json<SharedPlan>> {
+    tracing::info!(
+        request_id = %request_id,
+        slug = %slug,
+        "Fetching shared plan"
+    );
+
+    let plan = plans::fetch_plan(&state.db_pool, &slug).await?;
+
+    Ok(Json(plan))
+}
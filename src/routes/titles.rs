@@ -6,13 +6,19 @@ use serde::Deserialize;
 use std::sync::Arc;
 
 use crate::{
-    error::AppResult, middleware::request_id::RequestId, models::Title, routes::AppState,
+    error::AppResult,
+    middleware::request_id::RequestId,
+    models::{Country, Title},
+    routes::AppState,
     services::title_search,
 };
 
 #[derive(Debug, Deserialize)]
 pub struct SearchQuery {
     q: String,
+    /// Country whose catalog is searched (US if omitted)
+    #[serde(default)]
+    country: Country,
 }
 
 /// Handler for title search endpoint
@@ -24,10 +30,13 @@ pub async fn search(
     tracing::info!(
         request_id = %request_id,
         query = %params.q,
+        country = %params.country,
         "Processing title search request"
     );
 
-    let titles = title_search::search_titles(state.streaming_provider.clone(), &params.q).await?;
+    let titles =
+        title_search::search_titles(state.streaming_provider.clone(), &params.q, params.country)
+            .await?;
 
     tracing::info!(
         request_id = %request_id,
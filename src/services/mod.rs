@@ -1,4 +1,6 @@
+pub mod exchange_rates;
 pub mod optimization;
+pub mod plans;
 pub mod providers;
 pub mod recommendations;
 pub mod title_search;
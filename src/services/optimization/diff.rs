@@ -0,0 +1,377 @@
+/// What-if comparison of a watchlist before and after adding or removing titles
+///
+/// Availability is fetched once for every title of both watchlists, and both requests are
+/// solved against the same service catalog, so only the edit itself moves the result.
+use super::{
+    attach_availability_details, normalize_costs, prepare_catalog, solve_blocking,
+    solve_optimization, validate_options,
+};
+use crate::{
+    error::{AppError, AppResult},
+    models::{
+        OptimizationDiffRequest, OptimizationDiffResponse, OptimizationRequest,
+        OptimizationResponse, ServiceConfiguration, TitleId,
+    },
+    services::{exchange_rates, providers::StreamingProvider},
+};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Compares the optimal configuration of a request with that of its edited version
+pub async fn optimize_diff(
+    db_pool: Arc<PgPool>,
+    streaming_provider: Arc<dyn StreamingProvider>,
+    request: OptimizationDiffRequest,
+) -> AppResult<OptimizationDiffResponse> {
+    let start = Instant::now();
+
+    validate_diff_request(&request)?;
+    let updated = apply_edits(&request);
+
+    // Fetch availability and build the catalog once, for the titles of both requests
+    let mut combined = request.base.clone();
+    for title in updated.must_have.iter().chain(updated.nice_to_have.iter()) {
+        if !combined.must_have.contains(title) && !combined.nice_to_have.contains(title) {
+            combined.nice_to_have.push(title.clone());
+        }
+    }
+    let catalog = prepare_catalog(&db_pool, streaming_provider.as_ref(), &combined).await?;
+
+    // Unknown reporting currencies fail the request before solving
+    let reporting_rate = match &request.base.reporting_currency {
+        Some(reporting_currency) => Some(
+            exchange_rates::conversion_rate(
+                &db_pool,
+                &catalog.service_catalog.currency,
+                reporting_currency,
+            )
+            .await?,
+        ),
+        None => None,
+    };
+
+    let base = request.base.clone();
+    let (mut base_response, mut updated_response) = solve_blocking(move || {
+        let solve = |request: &OptimizationRequest| -> AppResult<OptimizationResponse> {
+            solve_optimization(
+                &catalog.service_catalog,
+                &catalog.title_to_services,
+                request,
+                unavailable(&request.must_have, &catalog.title_to_services),
+                unavailable(&request.nice_to_have, &catalog.title_to_services),
+            )
+        };
+        Ok((solve(&base)?, solve(&updated)?))
+    })
+    .await?;
+    attach_availability_details(
+        &mut updated_response.configurations,
+        &catalog.availability_details,
+    );
+    if let (Some(reporting_currency), Some(rate)) =
+        (&request.base.reporting_currency, reporting_rate)
+    {
+        normalize_costs(&mut base_response, reporting_currency, rate);
+        normalize_costs(&mut updated_response, reporting_currency, rate);
+    }
+
+    let response = diff_responses(base_response, updated_response);
+
+    tracing::info!(
+        cost_change = %response.cost_change,
+        services_added = response.services_added.len(),
+        services_dropped = response.services_dropped.len(),
+        processing_time_ms = start.elapsed().as_millis(),
+        "Optimization diff completed"
+    );
+
+    Ok(response)
+}
+
+/// Validates the base request and that the edit changes the watchlist
+fn validate_diff_request(request: &OptimizationDiffRequest) -> AppResult<()> {
+    validate_options(&request.base)?;
+
+    if request.add_must_have.is_empty()
+        && request.add_nice_to_have.is_empty()
+        && request.remove.is_empty()
+    {
+        return Err(AppError::InvalidInput(
+            "Must add or remove at least one title".to_string(),
+        ));
+    }
+
+    if let Some(title) = request
+        .add_must_have
+        .iter()
+        .chain(request.add_nice_to_have.iter())
+        .find(|title| request.remove.contains(title))
+    {
+        return Err(AppError::InvalidInput(format!(
+            "Title {} cannot be both added and removed",
+            title
+        )));
+    }
+
+    if let Some(title) = request
+        .add_must_have
+        .iter()
+        .find(|title| request.add_nice_to_have.contains(title))
+    {
+        return Err(AppError::InvalidInput(format!(
+            "Title {} cannot be added as both must-have and nice-to-have",
+            title
+        )));
+    }
+
+    Ok(())
+}
+
+/// Builds the edited request: removed titles are dropped along with their priority, and
+/// added titles are moved out of the other list if needed
+fn apply_edits(request: &OptimizationDiffRequest) -> OptimizationRequest {
+    let mut updated = request.base.clone();
+
+    updated.must_have.retain(|title| {
+        !request.remove.contains(title) && !request.add_nice_to_have.contains(title)
+    });
+    updated
+        .nice_to_have
+        .retain(|title| !request.remove.contains(title) && !request.add_must_have.contains(title));
+    for title in &request.remove {
+        updated.priorities.remove(&title.to_string());
+    }
+
+    for title in &request.add_must_have {
+        if !updated.must_have.contains(title) {
+            updated.must_have.push(title.clone());
+        }
+    }
+    for title in &request.add_nice_to_have {
+        if !updated.nice_to_have.contains(title) {
+            updated.nice_to_have.push(title.clone());
+        }
+    }
+
+    updated
+}
+
+/// Returns the titles without any known service
+fn unavailable(
+    titles: &[TitleId],
+    title_to_services: &HashMap<String, Vec<String>>,
+) -> Vec<TitleId> {
+    titles
+        .iter()
+        .filter(|title| !title_to_services.contains_key(&title.to_string()))
+        .cloned()
+        .collect()
+}
+
+/// Compares the optimal (first) configurations of the base and edited responses
+///
+/// A response without configurations counts as selecting nothing at no cost. The cost
+/// change is also reported in the reporting currency when both responses were normalized.
+fn diff_responses(
+    base: OptimizationResponse,
+    updated: OptimizationResponse,
+) -> OptimizationDiffResponse {
+    let base_optimal = base.configurations.into_iter().next();
+    let updated_optimal = updated.configurations.into_iter().next();
+
+    let ids = |configuration: &Option<ServiceConfiguration>| -> HashSet<String> {
+        configuration
+            .iter()
+            .flat_map(|c| c.services.iter())
+            .map(|s| s.id.clone())
+            .collect()
+    };
+    let base_ids = ids(&base_optimal);
+    let updated_ids = ids(&updated_optimal);
+
+    let mut services_added: Vec<String> = updated_ids.difference(&base_ids).cloned().collect();
+    let mut services_dropped: Vec<String> = base_ids.difference(&updated_ids).cloned().collect();
+    services_added.sort();
+    services_dropped.sort();
+
+    let stat = |configuration: &Option<ServiceConfiguration>| {
+        configuration.as_ref().map_or((Decimal::ZERO, 0, 0), |c| {
+            (
+                c.total_cost,
+                c.must_have_coverage as i64,
+                c.nice_to_have_coverage as i64,
+            )
+        })
+    };
+    let (base_cost, base_must_have, base_nice_to_have) = stat(&base_optimal);
+    let (updated_cost, updated_must_have, updated_nice_to_have) = stat(&updated_optimal);
+
+    let normalized_cost = |configuration: &Option<ServiceConfiguration>| {
+        configuration
+            .as_ref()
+            .map_or(Some(Decimal::ZERO), |c| c.normalized_total_cost)
+    };
+    let normalized_cost_change = match (&base.reporting_currency, &updated.reporting_currency) {
+        (Some(_), Some(_)) => normalized_cost(&updated_optimal)
+            .zip(normalized_cost(&base_optimal))
+            .map(|(updated_cost, base_cost)| updated_cost - base_cost),
+        _ => None,
+    };
+
+    OptimizationDiffResponse {
+        configuration: updated_optimal,
+        cost_change: updated_cost - base_cost,
+        currency: updated.currency,
+        reporting_currency: updated.reporting_currency,
+        normalized_cost_change,
+        services_added,
+        services_dropped,
+        must_have_coverage_change: updated_must_have - base_must_have,
+        nice_to_have_coverage_change: updated_nice_to_have - base_nice_to_have,
+        unavailable_must_have: updated.unavailable_must_have,
+        unavailable_nice_to_have: updated.unavailable_nice_to_have,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::optimization::{price, ServiceCatalog, ServiceInfo};
+
+    fn title(id: &str) -> TitleId {
+        TitleId::Imdb(id.to_string())
+    }
+
+    fn create_request(
+        must_have: Vec<&str>,
+        nice_to_have: Vec<&str>,
+        add_must_have: Vec<&str>,
+        remove: Vec<&str>,
+    ) -> OptimizationDiffRequest {
+        OptimizationDiffRequest {
+            base: OptimizationRequest {
+                must_have: must_have.into_iter().map(title).collect(),
+                nice_to_have: nice_to_have.into_iter().map(title).collect(),
+                ..Default::default()
+            },
+            add_must_have: add_must_have.into_iter().map(title).collect(),
+            add_nice_to_have: vec![],
+            remove: remove.into_iter().map(title).collect(),
+        }
+    }
+
+    #[test]
+    fn test_apply_edits_moves_and_removes_titles() {
+        let mut request = create_request(
+            vec!["tt1111111", "tt2222222"],
+            vec!["tt3333333"],
+            vec!["tt3333333"],
+            vec!["tt2222222"],
+        );
+        request.base.priorities.insert("tt2222222".to_string(), 5);
+
+        let updated = apply_edits(&request);
+
+        assert_eq!(
+            updated.must_have,
+            vec![title("tt1111111"), title("tt3333333")]
+        );
+        assert!(updated.nice_to_have.is_empty());
+        assert!(updated.priorities.is_empty());
+    }
+
+    #[test]
+    fn test_validate_diff_request_rejects_conflicting_edits() {
+        let request = create_request(vec!["tt1111111"], vec![], vec![], vec![]);
+        assert!(matches!(
+            validate_diff_request(&request),
+            Err(AppError::InvalidInput(_))
+        ));
+
+        let request = create_request(vec![], vec![], vec!["tt1111111"], vec!["tt1111111"]);
+        assert!(matches!(
+            validate_diff_request(&request),
+            Err(AppError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_diff_responses_reports_added_service() {
+        let service_catalog = ServiceCatalog::from(vec![
+            ServiceInfo::single_plan("netflix", "Netflix", 15.49),
+            ServiceInfo::single_plan("hulu", "Hulu", 7.99),
+        ]);
+        let mut title_to_services = HashMap::new();
+        title_to_services.insert("tt1111111".to_string(), vec!["hulu".to_string()]);
+        title_to_services.insert("tt2222222".to_string(), vec!["netflix".to_string()]);
+
+        let request = create_request(vec!["tt1111111"], vec![], vec!["tt2222222"], vec![]);
+        let updated = apply_edits(&request);
+
+        let base_response = solve_optimization(
+            &service_catalog,
+            &title_to_services,
+            &request.base,
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        let updated_response = solve_optimization(
+            &service_catalog,
+            &title_to_services,
+            &updated,
+            vec![],
+            vec![],
+        )
+        .unwrap();
+
+        let diff = diff_responses(base_response, updated_response);
+
+        assert_eq!(diff.cost_change, price(15.49));
+        assert_eq!(diff.services_added, vec!["netflix".to_string()]);
+        assert!(diff.services_dropped.is_empty());
+        assert_eq!(diff.must_have_coverage_change, 1);
+        assert_eq!(diff.nice_to_have_coverage_change, 0);
+        assert_eq!(
+            diff.configuration.unwrap().total_cost,
+            price(7.99) + price(15.49)
+        );
+    }
+
+    #[test]
+    fn test_diff_responses_reports_normalized_cost_change() {
+        let service_catalog = ServiceCatalog::from(vec![
+            ServiceInfo::single_plan("netflix", "Netflix", 15.49),
+            ServiceInfo::single_plan("hulu", "Hulu", 7.99),
+        ]);
+        let mut title_to_services = HashMap::new();
+        title_to_services.insert("tt1111111".to_string(), vec!["hulu".to_string()]);
+        title_to_services.insert("tt2222222".to_string(), vec!["netflix".to_string()]);
+
+        let request = create_request(vec!["tt1111111"], vec![], vec!["tt2222222"], vec![]);
+        let updated = apply_edits(&request);
+
+        let solve = |request: &OptimizationRequest| {
+            let mut response = solve_optimization(
+                &service_catalog,
+                &title_to_services,
+                request,
+                vec![],
+                vec![],
+            )
+            .unwrap();
+            normalize_costs(&mut response, "eur", Decimal::new(5, 1));
+            response
+        };
+        let diff = diff_responses(solve(&request.base), solve(&updated));
+
+        assert_eq!(diff.cost_change, price(15.49));
+        assert_eq!(diff.currency, "USD");
+        assert_eq!(diff.reporting_currency.as_deref(), Some("EUR"));
+        // 11.74 - 4.00, each total rounded to cents after converting
+        assert_eq!(diff.normalized_cost_change, Some(price(7.74)));
+    }
+}
@@ -0,0 +1,507 @@
+/// Household optimization over several members' watchlists
+///
+/// Member watchlists are merged into a single request: a title is a must-have when any
+/// member must have it, and its priority is the sum of the priorities of the members
+/// listing it. The combined request is solved like any other, and each configuration then
+/// reports how it covers every member and, optionally, how its cost splits between them.
+use super::{
+    attach_availability_details, normalize_costs, prepare_catalog, solve_blocking,
+    solve_optimization, validate_options, MAX_PRIORITY,
+};
+use crate::{
+    error::{AppError, AppResult},
+    models::{
+        CoverageRoute, HouseholdConfiguration, HouseholdMember, HouseholdRequest,
+        HouseholdResponse, MemberCoverage, OptimizationRequest, ServiceConfiguration, TitleId,
+    },
+    services::{exchange_rates, providers::StreamingProvider},
+};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Finds configurations covering every household member's must-haves
+pub async fn optimize_household(
+    db_pool: Arc<PgPool>,
+    streaming_provider: Arc<dyn StreamingProvider>,
+    request: HouseholdRequest,
+) -> AppResult<HouseholdResponse> {
+    let start = Instant::now();
+
+    validate_household_request(&request)?;
+    let combined = combined_request(&request);
+
+    let catalog = prepare_catalog(&db_pool, streaming_provider.as_ref(), &combined).await?;
+
+    // Unknown reporting currencies fail the request before solving
+    let reporting_rate = match &combined.reporting_currency {
+        Some(reporting_currency) => Some(
+            exchange_rates::conversion_rate(
+                &db_pool,
+                &catalog.service_catalog.currency,
+                reporting_currency,
+            )
+            .await?,
+        ),
+        None => None,
+    };
+
+    let solve_request = combined.clone();
+    let mut solution = solve_blocking(move || {
+        solve_optimization(
+            &catalog.service_catalog,
+            &catalog.title_to_services,
+            &solve_request,
+            catalog.unavailable_must_have,
+            catalog.unavailable_nice_to_have,
+        )
+    })
+    .await?;
+    attach_availability_details(&mut solution.configurations, &catalog.availability_details);
+    solution.availability_as_of = catalog.availability_as_of;
+    if let (Some(reporting_currency), Some(rate)) = (&combined.reporting_currency, reporting_rate) {
+        normalize_costs(&mut solution, reporting_currency, rate);
+    }
+
+    let unavailable: HashSet<&TitleId> = solution
+        .unavailable_must_have
+        .iter()
+        .chain(solution.unavailable_nice_to_have.iter())
+        .collect();
+    let configurations: Vec<HouseholdConfiguration> = solution
+        .configurations
+        .into_iter()
+        .map(|configuration| {
+            let mut members: Vec<MemberCoverage> = request
+                .members
+                .iter()
+                .map(|member| member_coverage(member, &configuration, &unavailable))
+                .collect();
+            if request.split_cost {
+                let shares = split_cost(&request.members, &configuration);
+                for (coverage, share) in members.iter_mut().zip(shares) {
+                    coverage.cost_share = Some(share);
+                }
+            }
+            HouseholdConfiguration {
+                configuration,
+                members,
+            }
+        })
+        .collect();
+
+    tracing::info!(
+        members = request.members.len(),
+        configurations = configurations.len(),
+        processing_time_ms = start.elapsed().as_millis(),
+        "Household optimization completed"
+    );
+
+    Ok(HouseholdResponse {
+        configurations,
+        country: solution.country,
+        currency: solution.currency,
+        reporting_currency: solution.reporting_currency,
+        horizon_months: solution.horizon_months,
+        unavailable_must_have: solution.unavailable_must_have,
+        unavailable_nice_to_have: solution.unavailable_nice_to_have,
+        availability_as_of: solution.availability_as_of,
+    })
+}
+
+/// Validates the shared options and every member's watchlist
+fn validate_household_request(request: &HouseholdRequest) -> AppResult<()> {
+    validate_options(&request.options)?;
+
+    if !request.options.must_have.is_empty()
+        || !request.options.nice_to_have.is_empty()
+        || !request.options.priorities.is_empty()
+    {
+        return Err(AppError::InvalidInput(
+            "Titles and priorities must be given per member".to_string(),
+        ));
+    }
+
+    if request.members.is_empty() {
+        return Err(AppError::InvalidInput(
+            "Household must have at least one member".to_string(),
+        ));
+    }
+
+    let mut names = HashSet::new();
+    for member in &request.members {
+        if member.name.trim().is_empty() {
+            return Err(AppError::InvalidInput(
+                "Member name must not be empty".to_string(),
+            ));
+        }
+        if !names.insert(member.name.as_str()) {
+            return Err(AppError::InvalidInput(format!(
+                "Member name {} is used more than once",
+                member.name
+            )));
+        }
+
+        if let Some((title, _)) = member
+            .priorities
+            .iter()
+            .find(|(_, &priority)| priority == 0 || priority > MAX_PRIORITY)
+        {
+            return Err(AppError::InvalidInput(format!(
+                "Priority for title {} of member {} must be between 1 and {}",
+                title, member.name, MAX_PRIORITY
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Merges the member watchlists into a single request with the shared options
+///
+/// A title is a must-have when any member must have it, and a nice-to-have otherwise.
+/// Its priority is the sum of the priorities of the members listing it, so titles wanted
+/// by more members weigh more.
+fn combined_request(request: &HouseholdRequest) -> OptimizationRequest {
+    let mut combined = request.options.clone();
+
+    for member in &request.members {
+        for title in &member.must_have {
+            if !combined.must_have.contains(title) {
+                combined.must_have.push(title.clone());
+            }
+        }
+    }
+    for member in &request.members {
+        for title in &member.nice_to_have {
+            if !combined.must_have.contains(title) && !combined.nice_to_have.contains(title) {
+                combined.nice_to_have.push(title.clone());
+            }
+        }
+    }
+
+    for member in &request.members {
+        let titles: HashSet<String> = member
+            .must_have
+            .iter()
+            .chain(member.nice_to_have.iter())
+            .map(|title| title.to_string())
+            .collect();
+        for title in titles {
+            let priority = member.priorities.get(&title).copied().unwrap_or(1);
+            let total = combined.priorities.entry(title).or_insert(0);
+            *total = total.saturating_add(priority);
+        }
+    }
+
+    combined
+}
+
+/// Coverage of one member's watchlist by a household configuration
+///
+/// Unrequested fields such as `cost_share` are left unset.
+fn member_coverage(
+    member: &HouseholdMember,
+    configuration: &ServiceConfiguration,
+    unavailable: &HashSet<&TitleId>,
+) -> MemberCoverage {
+    let covered: HashSet<&TitleId> = configuration
+        .title_routes
+        .iter()
+        .map(|route| &route.title)
+        .collect();
+    let priority = |title: &TitleId| {
+        member
+            .priorities
+            .get(&title.to_string())
+            .copied()
+            .unwrap_or(1) as f64
+    };
+
+    MemberCoverage {
+        name: member.name.clone(),
+        must_have_coverage: member
+            .must_have
+            .iter()
+            .filter(|title| covered.contains(title))
+            .count(),
+        nice_to_have_coverage: member
+            .nice_to_have
+            .iter()
+            .filter(|title| covered.contains(title))
+            .count(),
+        nice_to_have_weighted_coverage: member
+            .nice_to_have
+            .iter()
+            .filter(|title| covered.contains(title))
+            .map(priority)
+            .sum(),
+        dropped_must_have: member
+            .must_have
+            .iter()
+            .filter(|title| configuration.dropped_must_have.contains(title))
+            .cloned()
+            .collect(),
+        uncovered_nice_to_have: member
+            .nice_to_have
+            .iter()
+            .filter(|title| !covered.contains(title) && !unavailable.contains(title))
+            .cloned()
+            .collect(),
+        cost_share: None,
+    }
+}
+
+/// Splits the total cost of a configuration between members, in member order
+///
+/// Each service (or the bundle it is held through) is split in proportion to the number
+/// of titles each member watches on it, and each rental or purchase between the members
+/// listing its title. Services nobody watches anything on, such as included or kept
+/// current services, are split evenly. Shares are rounded to cents, and any rounding
+/// difference goes to the largest share so they add up to the total cost.
+fn split_cost(members: &[HouseholdMember], configuration: &ServiceConfiguration) -> Vec<Decimal> {
+    // Cost of each service, add-on or bundle held, keyed by the ID it is paid under
+    let mut item_costs: HashMap<&str, Decimal> = HashMap::new();
+    for service in &configuration.services {
+        match &service.bundle_id {
+            Some(bundle_id) => {
+                let bundle_cost = configuration
+                    .bundles
+                    .iter()
+                    .find(|bundle| &bundle.id == bundle_id)
+                    .map_or(Decimal::ZERO, |bundle| bundle.monthly_cost);
+                item_costs.insert(bundle_id.as_str(), bundle_cost);
+            }
+            None => {
+                item_costs.insert(service.id.as_str(), service.monthly_cost);
+            }
+        }
+    }
+
+    // Titles each member watches on each paid item
+    let mut usage: HashMap<&str, Vec<u32>> = HashMap::new();
+    let mut shares = vec![Decimal::ZERO; members.len()];
+    for route in &configuration.title_routes {
+        let watchers: Vec<usize> = members
+            .iter()
+            .enumerate()
+            .filter(|(_, member)| {
+                member.must_have.contains(&route.title)
+                    || member.nice_to_have.contains(&route.title)
+            })
+            .map(|(index, _)| index)
+            .collect();
+        if watchers.is_empty() {
+            continue;
+        }
+
+        match (&route.route, route.price) {
+            // A rental or purchase is only watched by the members listing its title
+            (CoverageRoute::Rent | CoverageRoute::Buy, Some(price)) => {
+                let share = price / Decimal::from(watchers.len());
+                for index in watchers {
+                    shares[index] += share;
+                }
+            }
+            (CoverageRoute::Subscription, _) => {
+                let item = configuration
+                    .services
+                    .iter()
+                    .find(|service| service.id == route.service_id)
+                    .map(|service| service.bundle_id.as_deref().unwrap_or(&service.id));
+                if let Some(item) = item {
+                    let counts = usage.entry(item).or_insert_with(|| vec![0; members.len()]);
+                    for index in watchers {
+                        counts[index] += 1;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for (item, cost) in item_costs {
+        match usage.get(item) {
+            Some(counts) => {
+                let total: u32 = counts.iter().sum();
+                for (share, &count) in shares.iter_mut().zip(counts) {
+                    *share += cost * Decimal::from(count) / Decimal::from(total);
+                }
+            }
+            None => {
+                let even = cost / Decimal::from(members.len());
+                for share in &mut shares {
+                    *share += even;
+                }
+            }
+        }
+    }
+
+    let mut shares: Vec<Decimal> = shares.into_iter().map(|share| share.round_dp(2)).collect();
+    let remainder = configuration.total_cost - shares.iter().sum::<Decimal>();
+    if let Some(largest) = shares.iter_mut().max() {
+        *largest += remainder;
+    }
+    shares
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::optimization::{price, ServiceCatalog, ServiceInfo};
+
+    fn title(id: &str) -> TitleId {
+        TitleId::Imdb(id.to_string())
+    }
+
+    fn member(name: &str, must_have: Vec<&str>, nice_to_have: Vec<&str>) -> HouseholdMember {
+        HouseholdMember {
+            name: name.to_string(),
+            must_have: must_have.into_iter().map(title).collect(),
+            nice_to_have: nice_to_have.into_iter().map(title).collect(),
+            priorities: HashMap::new(),
+        }
+    }
+
+    fn create_request(members: Vec<HouseholdMember>) -> HouseholdRequest {
+        HouseholdRequest {
+            members,
+            options: OptimizationRequest::default(),
+            split_cost: true,
+        }
+    }
+
+    #[test]
+    fn test_combined_request_merges_member_watchlists() {
+        let mut alice = member("Alice", vec!["tt1111111"], vec!["tt2222222", "tt3333333"]);
+        alice.priorities.insert("tt3333333".to_string(), 4);
+        let mut bob = member("Bob", vec!["tt2222222"], vec!["tt3333333"]);
+        bob.priorities.insert("tt3333333".to_string(), 3);
+
+        let combined = combined_request(&create_request(vec![alice, bob]));
+
+        // A must-have for anyone is a must-have for the household
+        assert_eq!(
+            combined.must_have,
+            vec![title("tt1111111"), title("tt2222222")]
+        );
+        assert_eq!(combined.nice_to_have, vec![title("tt3333333")]);
+
+        // Priorities add up across members, defaulting to 1
+        assert_eq!(combined.priorities["tt1111111"], 1);
+        assert_eq!(combined.priorities["tt2222222"], 2);
+        assert_eq!(combined.priorities["tt3333333"], 7);
+    }
+
+    #[test]
+    fn test_validate_household_request() {
+        let valid = create_request(vec![member("Alice", vec!["tt1111111"], vec![])]);
+        assert!(validate_household_request(&valid).is_ok());
+
+        let no_members = create_request(vec![]);
+        let duplicate_names = create_request(vec![
+            member("Alice", vec!["tt1111111"], vec![]),
+            member("Alice", vec!["tt2222222"], vec![]),
+        ]);
+        let mut top_level_titles = create_request(vec![member("Alice", vec![], vec![])]);
+        top_level_titles.options.must_have = vec![title("tt1111111")];
+        let mut bad_priority = member("Alice", vec!["tt1111111"], vec![]);
+        bad_priority
+            .priorities
+            .insert("tt1111111".to_string(), MAX_PRIORITY + 1);
+        let bad_priority = create_request(vec![bad_priority]);
+
+        for request in [no_members, duplicate_names, top_level_titles, bad_priority] {
+            assert!(matches!(
+                validate_household_request(&request),
+                Err(AppError::InvalidInput(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_member_coverage_and_cost_split() {
+        // Alice watches one title on each service, Bob two on Netflix and one on Hulu
+        let service_catalog = ServiceCatalog::from(vec![
+            ServiceInfo::single_plan("netflix", "Netflix", 15.49),
+            ServiceInfo::single_plan("hulu", "Hulu", 7.99),
+        ]);
+        let mut title_to_services = HashMap::new();
+        for (id, service) in [
+            ("tt1111111", "netflix"),
+            ("tt2222222", "netflix"),
+            ("tt3333333", "netflix"),
+            ("tt4444444", "hulu"),
+            ("tt5555555", "hulu"),
+        ] {
+            title_to_services.insert(id.to_string(), vec![service.to_string()]);
+        }
+
+        let request = create_request(vec![
+            member("Alice", vec!["tt1111111"], vec!["tt4444444"]),
+            member("Bob", vec!["tt2222222", "tt3333333", "tt5555555"], vec![]),
+        ]);
+        let combined = combined_request(&request);
+        let solution = solve_optimization(
+            &service_catalog,
+            &title_to_services,
+            &combined,
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        let configuration = &solution.configurations[0];
+        assert_eq!(configuration.total_cost, price(23.48));
+
+        let unavailable = HashSet::new();
+        let alice = member_coverage(&request.members[0], configuration, &unavailable);
+        assert_eq!(alice.must_have_coverage, 1);
+        assert_eq!(alice.nice_to_have_coverage, 1);
+        assert_eq!(alice.nice_to_have_weighted_coverage, 1.0);
+        assert!(alice.uncovered_nice_to_have.is_empty());
+        let bob = member_coverage(&request.members[1], configuration, &unavailable);
+        assert_eq!(bob.must_have_coverage, 3);
+        assert_eq!(bob.nice_to_have_coverage, 0);
+
+        // Netflix splits 1:2 and Hulu 1:1
+        let shares = split_cost(&request.members, configuration);
+        assert_eq!(shares, vec![price(9.16), price(14.32)]);
+        assert_eq!(shares.iter().sum::<Decimal>(), configuration.total_cost);
+    }
+
+    #[test]
+    fn test_split_cost_shares_unused_services_evenly() {
+        // Hulu is pinned in but nobody watches anything on it
+        let mut service_catalog = ServiceCatalog::from(vec![
+            ServiceInfo::single_plan("netflix", "Netflix", 15.49),
+            ServiceInfo::single_plan("hulu", "Hulu", 7.99),
+        ]);
+        service_catalog.required.insert("hulu".to_string());
+        let mut title_to_services = HashMap::new();
+        title_to_services.insert("tt1111111".to_string(), vec!["netflix".to_string()]);
+
+        let request = create_request(vec![
+            member("Alice", vec!["tt1111111"], vec![]),
+            member("Bob", vec![], vec![]),
+            member("Carol", vec![], vec![]),
+        ]);
+        let combined = combined_request(&request);
+        let solution = solve_optimization(
+            &service_catalog,
+            &title_to_services,
+            &combined,
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        let configuration = &solution.configurations[0];
+
+        // 15.49 + 7.99 / 3 for Alice, 7.99 / 3 (2.6633..) each for Bob and Carol; the
+        // rounding cent goes to Alice
+        let shares = split_cost(&request.members, configuration);
+        assert_eq!(shares, vec![price(18.16), price(2.66), price(2.66)]);
+        assert_eq!(shares.iter().sum::<Decimal>(), price(23.48));
+    }
+}
@@ -0,0 +1,1094 @@
+/// Solver backends for the cheapest configuration covering every must-have title
+///
+/// `find_solution` describes its problem as a `CoverProblem` and hands it to one of three
+/// backends: the exact integer programming model, a greedy weighted set-cover heuristic
+/// that scales to large catalogs, or an exhaustive enumerator for tiny catalogs. The
+/// integer programming solver is stopped at the request's time limit, and the greedy
+/// heuristic takes over once the limit is exceeded.
+use super::{
+    add_selection_vars, add_title_coverage_vars, priority_for_key, solver_cost, ServiceCatalog,
+    ServiceInfo, SolutionCuts, COVERAGE_TIE_BREAK, FREE_PLAN_BONUS,
+};
+use crate::{
+    error::{AppError, AppResult},
+    models::{CoverageRoute, OptimizationRequest, SolverBackend, TitleId},
+};
+use good_lp::solvers::{Solution, SolutionStatus, WithTimeLimit};
+use good_lp::{default_solver, Expression, ProblemVariables, ResolutionError, SolverModel};
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+/// Time limit applied when the request does not set one
+const DEFAULT_TIME_LIMIT_MS: u64 = 10_000;
+
+/// Most optional plans, bundles, add-ons and one-offs the exhaustive backend enumerates
+const EXHAUSTIVE_MAX_ITEMS: usize = 16;
+
+/// Extra penalty per held service under a simplicity tolerance, so fewer services also
+/// win when they cost exactly the tolerance more
+const SIMPLICITY_TIE_BREAK: f64 = 1e-6;
+
+/// Solver settings shared by every solve of a request
+#[derive(Debug, Clone)]
+pub(super) struct SolveOptions {
+    pub(super) backend: SolverBackend,
+    pub(super) simplicity_tolerance: Decimal,
+    /// Point after which the integer programming backend gives way to the greedy
+    /// heuristic (`None` when the limit is too far away to represent)
+    pub(super) deadline: Option<Instant>,
+}
+
+impl SolveOptions {
+    /// Reads the solver settings of a request, starting its time limit now
+    ///
+    /// Fails when the exhaustive backend is requested for a catalog too large to enumerate.
+    pub(super) fn new(
+        request: &OptimizationRequest,
+        service_catalog: &ServiceCatalog,
+    ) -> AppResult<Self> {
+        if request.solver == SolverBackend::Exhaustive {
+            let items = optional_items(service_catalog).len();
+            if items > EXHAUSTIVE_MAX_ITEMS {
+                return Err(AppError::InvalidInput(format!(
+                    "The exhaustive solver supports at most {} plans, bundles, add-ons and one-offs, but this request has {}",
+                    EXHAUSTIVE_MAX_ITEMS, items
+                )));
+            }
+        }
+
+        let time_limit =
+            Duration::from_millis(request.time_limit_ms.unwrap_or(DEFAULT_TIME_LIMIT_MS));
+        Ok(SolveOptions {
+            backend: request.solver,
+            simplicity_tolerance: request.simplicity_tolerance.unwrap_or_default(),
+            deadline: Instant::now().checked_add(time_limit),
+        })
+    }
+}
+
+/// The cheapest-configuration problem solved by `find_solution`
+pub(super) struct CoverProblem<'a> {
+    pub(super) service_catalog: &'a ServiceCatalog,
+    pub(super) title_to_services: &'a HashMap<String, Vec<String>>,
+    pub(super) available_must_have: &'a [&'a TitleId],
+    pub(super) nice_to_have: &'a [TitleId],
+    pub(super) priorities: &'a HashMap<String, u8>,
+    pub(super) cuts: &'a SolutionCuts,
+    pub(super) simplicity_tolerance: Decimal,
+}
+
+/// Plans, bundles, add-ons and one-offs picked by a solver backend
+#[derive(Debug, Clone, Default)]
+pub(super) struct Choice {
+    /// Standalone plans, by plan ID
+    pub(super) plans: HashSet<String>,
+    /// Bundles, by bundle ID
+    pub(super) bundles: HashSet<String>,
+    /// Add-on channels and one-offs, by their IDs in the title mappings
+    pub(super) extras: HashSet<String>,
+}
+
+impl Choice {
+    fn add(&mut self, other: &Choice) {
+        self.plans.extend(other.plans.iter().cloned());
+        self.bundles.extend(other.bundles.iter().cloned());
+        self.extras.extend(other.extras.iter().cloned());
+    }
+
+    fn without(&self, other: &Choice) -> Choice {
+        Choice {
+            plans: self.plans.difference(&other.plans).cloned().collect(),
+            bundles: self.bundles.difference(&other.bundles).cloned().collect(),
+            extras: self.extras.difference(&other.extras).cloned().collect(),
+        }
+    }
+
+    /// IDs of the held services, add-ons and one-offs, as used in title mappings
+    fn ids(&self, service_catalog: &ServiceCatalog) -> HashSet<String> {
+        let mut ids: HashSet<String> = self.extras.clone();
+        for plan in &service_catalog.plans {
+            if self.plans.contains(&plan.plan_id) {
+                ids.insert(plan.id.clone());
+            }
+        }
+        for bundle in &service_catalog.bundles {
+            if self.bundles.contains(&bundle.id) {
+                ids.extend(bundle.members.iter().map(|member| member.id.clone()));
+            }
+        }
+        ids
+    }
+
+    /// Combined price of the chosen plans, bundles, add-ons and one-offs
+    fn cost(&self, service_catalog: &ServiceCatalog) -> Decimal {
+        let plans = service_catalog
+            .plans
+            .iter()
+            .filter(|plan| self.plans.contains(&plan.plan_id))
+            .map(|plan| plan.cost);
+        let bundles = service_catalog
+            .bundles
+            .iter()
+            .filter(|bundle| self.bundles.contains(&bundle.id))
+            .map(|bundle| bundle.cost);
+        let addons = service_catalog
+            .addons
+            .iter()
+            .filter(|addon| self.extras.contains(&addon.id))
+            .map(|addon| addon.cost);
+        let one_offs = service_catalog
+            .one_offs
+            .iter()
+            .filter(|one_off| self.extras.contains(&one_off.id))
+            .map(|one_off| one_off.price);
+        plans.chain(bundles).chain(addons).chain(one_offs).sum()
+    }
+
+    /// Number of standalone plans or bundles holding each service
+    fn sources_per_service<'a>(
+        &self,
+        service_catalog: &'a ServiceCatalog,
+    ) -> HashMap<&'a str, usize> {
+        let mut sources: HashMap<&str, usize> = HashMap::new();
+        for plan in &service_catalog.plans {
+            if self.plans.contains(&plan.plan_id) {
+                *sources.entry(plan.id.as_str()).or_default() += 1;
+            }
+        }
+        for bundle in &service_catalog.bundles {
+            if self.bundles.contains(&bundle.id) {
+                for member in &bundle.members {
+                    *sources.entry(member.id.as_str()).or_default() += 1;
+                }
+            }
+        }
+        sources
+    }
+
+    /// Number of services and add-on channels held, leaving out one-offs
+    fn held_count(&self, service_catalog: &ServiceCatalog) -> usize {
+        let addons = service_catalog
+            .addons
+            .iter()
+            .filter(|addon| self.extras.contains(&addon.id))
+            .count();
+        self.sources_per_service(service_catalog).len() + addons
+    }
+}
+
+/// A choice along with the backend that made it
+pub(super) struct SolvedChoice {
+    pub(super) choice: Choice,
+    pub(super) backend: SolverBackend,
+    pub(super) proven_optimal: bool,
+}
+
+/// How an integer programming solve ended
+pub(super) enum MilpOutcome<T> {
+    Solved(T),
+    /// No selection meets the constraints
+    Infeasible,
+    /// The solver was stopped at the deadline before proving a solution optimal
+    TimedOut,
+}
+
+/// Solves an integer programming model, stopping the solver once the deadline passes
+pub(super) fn solve_model<M>(
+    model: M,
+    deadline: Option<Instant>,
+) -> AppResult<MilpOutcome<M::Solution>>
+where
+    M: SolverModel<Error = ResolutionError> + WithTimeLimit,
+{
+    let model = match deadline {
+        None => model,
+        Some(deadline) => {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(MilpOutcome::TimedOut);
+            }
+            model.with_time_limit(remaining.as_secs_f64())
+        }
+    };
+    let timed_out = || deadline.is_some_and(|deadline| Instant::now() >= deadline);
+
+    match model.solve() {
+        Ok(solution) if matches!(solution.status(), SolutionStatus::Optimal) => {
+            Ok(MilpOutcome::Solved(solution))
+        }
+        // Stopped with a feasible but unproven incumbent
+        Ok(_) => Ok(MilpOutcome::TimedOut),
+        Err(ResolutionError::Infeasible) => Ok(MilpOutcome::Infeasible),
+        // Stopped before any feasible solution was found
+        Err(ResolutionError::Other(_)) if timed_out() => Ok(MilpOutcome::TimedOut),
+        Err(e) => Err(AppError::Optimization(format!("Solver failed: {}", e))),
+    }
+}
+
+/// Solves the problem with the requested backend
+///
+/// Returns `None` when no selection meets the constraints (for the greedy heuristic, when
+/// it finds none); any other solver failure is an error. The integer programming backend
+/// falls back to the greedy heuristic once the request's time limit is exceeded, so later
+/// solves of the same request go straight to greedy.
+pub(super) fn solve(
+    problem: &CoverProblem,
+    options: &SolveOptions,
+) -> AppResult<Option<SolvedChoice>> {
+    let solved = match options.backend {
+        SolverBackend::Milp => match milp(problem, options.deadline)? {
+            MilpOutcome::Solved(choice) => Some((choice, SolverBackend::Milp, true)),
+            MilpOutcome::Infeasible => None,
+            MilpOutcome::TimedOut => {
+                tracing::warn!("MILP solver exceeded the time limit, falling back to greedy");
+                greedy(problem).map(|choice| (choice, SolverBackend::Greedy, false))
+            }
+        },
+        SolverBackend::Greedy => {
+            greedy(problem).map(|choice| (choice, SolverBackend::Greedy, false))
+        }
+        SolverBackend::Exhaustive => {
+            exhaustive(problem)?.map(|choice| (choice, SolverBackend::Exhaustive, true))
+        }
+    };
+
+    Ok(
+        solved.map(|(choice, backend, proven_optimal)| SolvedChoice {
+            choice,
+            backend,
+            proven_optimal,
+        }),
+    )
+}
+
+/// Solves the problem exactly with integer programming, stopping at the deadline
+fn milp(problem: &CoverProblem, deadline: Option<Instant>) -> AppResult<MilpOutcome<Choice>> {
+    let service_catalog = problem.service_catalog;
+    let mut vars = ProblemVariables::new();
+
+    // Build constraints
+    let mut constraints = vec![];
+
+    // Create binary variables for each service, plan and bundle (0 = not selected, 1 = selected)
+    let selection = add_selection_vars(&mut vars, &mut constraints, service_catalog);
+    let service_vars = &selection.services;
+
+    // Constraint: Each available must-have title must be covered by at least one selected service
+    for title in problem.available_must_have {
+        if let Some(services) = problem.title_to_services.get(&title.to_string()) {
+            let mut coverage_expr = Expression::from(0);
+            for service_id in services {
+                if let Some(&var) = service_vars.get(service_id) {
+                    coverage_expr += var;
+                }
+            }
+            // At least one service must cover this title
+            constraints.push(coverage_expr.geq(1));
+        }
+    }
+
+    // Weighted nice-to-have coverage, one binary variable per available title
+    let nice_to_have_vars = add_title_coverage_vars(
+        &mut vars,
+        &mut constraints,
+        problem.nice_to_have.iter(),
+        problem.title_to_services,
+        service_vars,
+    );
+    let mut weighted_coverage = Expression::from(0);
+    let mut max_weighted_coverage = 0.0;
+    for (title, &var) in &nice_to_have_vars {
+        let priority = priority_for_key(problem.priorities, title);
+        weighted_coverage += priority * var;
+        max_weighted_coverage += priority;
+    }
+
+    // Constraint: Reach the requested coverage level (used to step along the frontier)
+    if let Some(min_coverage) = problem.cuts.min_weighted_coverage {
+        constraints.push(weighted_coverage.clone().geq(min_coverage));
+    }
+
+    // Constraint (no-good cut): flip at least one selection of every excluded set
+    for excluded in &problem.cuts.excluded_selections {
+        let mut flipped = Expression::from(0);
+        for (id, &var) in service_vars {
+            if excluded.contains(id) {
+                flipped += 1 - var;
+            } else {
+                flipped += var;
+            }
+        }
+        constraints.push(flipped.geq(1));
+    }
+
+    // Objective: Minimize cost, then maximize weighted nice-to-have coverage and keep
+    // zero-cost plans. Both bonuses stay below one cent, so they only break cost ties.
+    let mut objective = selection.cost(service_catalog);
+    objective -= COVERAGE_TIE_BREAK / (max_weighted_coverage + 1.0) * weighted_coverage;
+    objective -= FREE_PLAN_BONUS * selection.free_plans(service_catalog);
+    if !problem.simplicity_tolerance.is_zero() {
+        objective += problem.held_penalty() * selection.held_count(service_catalog);
+    }
+
+    // Build and solve the problem
+    let mut model = vars.minimise(objective).using(default_solver);
+    for constraint in constraints {
+        model = model.with(constraint);
+    }
+
+    Ok(match solve_model(model, deadline)? {
+        MilpOutcome::Solved(solution) => {
+            MilpOutcome::Solved(selection.choice(&solution, service_catalog))
+        }
+        MilpOutcome::Infeasible => MilpOutcome::Infeasible,
+        MilpOutcome::TimedOut => MilpOutcome::TimedOut,
+    })
+}
+
+/// A group of items the greedy heuristic adds in one step
+struct Step {
+    choice: Choice,
+    /// Services, add-ons and one-offs the step starts holding
+    ids: HashSet<String>,
+    /// Cost as ranked by the heuristic
+    cost: f64,
+    /// Services and add-ons the step adds towards `max_services`
+    held: usize,
+}
+
+impl Step {
+    fn plan(plan: &ServiceInfo) -> Self {
+        Step {
+            choice: Choice {
+                plans: HashSet::from([plan.plan_id.clone()]),
+                ..Default::default()
+            },
+            ids: HashSet::from([plan.id.clone()]),
+            cost: solver_cost(plan.cost),
+            held: 1,
+        }
+    }
+
+    fn extra(id: &str, cost: Decimal, held: usize) -> Self {
+        Step {
+            choice: Choice {
+                extras: HashSet::from([id.to_string()]),
+                ..Default::default()
+            },
+            ids: HashSet::from([id.to_string()]),
+            cost: solver_cost(cost),
+            held,
+        }
+    }
+}
+
+/// How the greedy heuristic ranks candidate steps
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum StepRank {
+    /// Lowest cost per unit of gain first
+    CostPerGain,
+    /// Largest gain first, cheapest among equals
+    Gain,
+}
+
+/// Finds a good selection quickly with a greedy weighted set-cover heuristic
+///
+/// Pinned services are bought first. The heuristic then repeatedly takes the step with
+/// the lowest cost per newly covered must-have title until all are covered, then per
+/// newly covered weighted nice-to-have coverage until the coverage floor is reached.
+/// A step is a service's cheapest standalone plan, a bundle, an add-on (with its parent
+/// service when not held yet) or a one-off. Finally, steps that turned out redundant are
+/// dropped, most expensive first, and zero-cost plans are kept where they fit. The result
+/// is not proven optimal.
+///
+/// Cheap steps covering little can use up a `max_services` cap early, so when that pass
+/// fails under a cap, a second pass takes the step covering the most first. Returns
+/// `None` when neither pass finds a selection meeting the constraints.
+fn greedy(problem: &CoverProblem) -> Option<Choice> {
+    greedy_pass(problem, StepRank::CostPerGain).or_else(|| {
+        problem
+            .service_catalog
+            .max_services
+            .and_then(|_| greedy_pass(problem, StepRank::Gain))
+    })
+}
+
+/// Runs the greedy heuristic once, ranking candidate steps by `rank`
+fn greedy_pass(problem: &CoverProblem, rank: StepRank) -> Option<Choice> {
+    let service_catalog = problem.service_catalog;
+    let mut choice = problem.forced_choice();
+    let mut steps: Vec<Step> = Vec::new();
+
+    // Pinned services and add-ons, each through its cheapest step
+    let mut required: Vec<&String> = service_catalog.required.iter().collect();
+    required.sort();
+    for id in required {
+        let ids = choice.ids(service_catalog);
+        if ids.contains(id) {
+            continue;
+        }
+        let step = problem
+            .greedy_steps(&ids)
+            .into_iter()
+            .filter(|step| step.ids.contains(id))
+            .min_by(|a, b| a.cost.total_cmp(&b.cost))?;
+        choice.add(&step.choice);
+    }
+
+    // Cover every must-have, cheapest step per newly covered title first
+    loop {
+        let ids = choice.ids(service_catalog);
+        let uncovered: Vec<String> = problem
+            .must_have_keys()
+            .filter(|key| !problem.covers(&ids, key))
+            .collect();
+        if uncovered.is_empty() {
+            break;
+        }
+
+        let step = problem.best_step(&choice, &ids, rank, |step| {
+            uncovered
+                .iter()
+                .filter(|key| problem.covers(&step.ids, key))
+                .count() as f64
+        })?;
+        choice.add(&step.choice);
+        steps.push(step);
+    }
+
+    // Reach the coverage floor, cheapest step per newly covered priority first
+    if let Some(min_coverage) = problem.cuts.min_weighted_coverage {
+        loop {
+            let ids = choice.ids(service_catalog);
+            if problem.weighted_coverage(&ids) >= min_coverage {
+                break;
+            }
+
+            let uncovered: Vec<String> = problem
+                .nice_to_have_keys()
+                .into_iter()
+                .filter(|key| !problem.covers(&ids, key))
+                .collect();
+            let step = problem.best_step(&choice, &ids, rank, |step| {
+                uncovered
+                    .iter()
+                    .filter(|key| problem.covers(&step.ids, key))
+                    .map(|key| priority_for_key(problem.priorities, key))
+                    .sum()
+            })?;
+            choice.add(&step.choice);
+            steps.push(step);
+        }
+    }
+
+    // Drop steps that later steps made redundant, most expensive first
+    steps.sort_by(|a, b| b.cost.total_cmp(&a.cost));
+    for step in &steps {
+        let candidate = choice.without(&step.choice);
+        if problem.is_feasible(&candidate) {
+            choice = candidate;
+        }
+    }
+
+    problem.keep_free_plans(&mut choice);
+
+    if !problem.is_feasible(&choice) || problem.is_cut(&choice.ids(service_catalog)) {
+        return None;
+    }
+
+    Some(choice)
+}
+
+/// Finds a selection with good weighted coverage within the budget quickly, for budget
+/// mode once the integer programming backend has run out of time
+///
+/// Pinned services are bought first, and `None` is returned when they alone cost more
+/// than the budget. The heuristic then repeatedly takes the step with the lowest cost per
+/// newly covered weighted title that still fits the budget and the service cap, with
+/// every must-have title outweighing all nice-to-haves combined, until no step adds
+/// coverage. Finally, zero-cost plans are kept where they fit. The result is not proven
+/// optimal.
+pub(super) fn greedy_budget(problem: &CoverProblem, budget: Decimal) -> Option<Choice> {
+    let service_catalog = problem.service_catalog;
+    let mut choice = problem.forced_choice();
+
+    let mut required: Vec<&String> = service_catalog.required.iter().collect();
+    required.sort();
+    for id in required {
+        let ids = choice.ids(service_catalog);
+        if ids.contains(id) {
+            continue;
+        }
+        let step = problem
+            .greedy_steps(&ids)
+            .into_iter()
+            .filter(|step| step.ids.contains(id))
+            .min_by(|a, b| a.cost.total_cmp(&b.cost))?;
+        choice.add(&step.choice);
+    }
+    if choice.cost(service_catalog) > budget {
+        return None;
+    }
+
+    let nice_to_have_total: f64 = problem
+        .nice_to_have_keys()
+        .iter()
+        .map(|key| priority_for_key(problem.priorities, key))
+        .sum();
+    loop {
+        let ids = choice.ids(service_catalog);
+        let remaining = budget - choice.cost(service_catalog);
+        let uncovered_must_have: Vec<String> = problem
+            .must_have_keys()
+            .filter(|key| !problem.covers(&ids, key))
+            .collect();
+        let uncovered_nice_to_have: Vec<String> = problem
+            .nice_to_have_keys()
+            .into_iter()
+            .filter(|key| !problem.covers(&ids, key))
+            .collect();
+
+        let step = problem.best_step(&choice, &ids, StepRank::CostPerGain, |step| {
+            if step.choice.cost(service_catalog) > remaining {
+                return 0.0;
+            }
+            let covered = |keys: &[String]| -> f64 {
+                keys.iter()
+                    .filter(|key| problem.covers(&step.ids, key))
+                    .map(|key| priority_for_key(problem.priorities, key))
+                    .sum()
+            };
+            (nice_to_have_total + 1.0) * covered(&uncovered_must_have)
+                + covered(&uncovered_nice_to_have)
+        });
+        match step {
+            Some(step) => choice.add(&step.choice),
+            None => break,
+        }
+    }
+
+    problem.keep_free_plans(&mut choice);
+    Some(choice)
+}
+
+/// Finds the optimal selection of a tiny catalog by checking every one of them
+///
+/// Free sources are always kept, so only the plans, bundles, add-ons and priced one-offs
+/// are enumerated (at most `EXHAUSTIVE_MAX_ITEMS` of them). Returns `None` when no
+/// selection meets the constraints.
+fn exhaustive(problem: &CoverProblem) -> AppResult<Option<Choice>> {
+    let service_catalog = problem.service_catalog;
+    let items = optional_items(service_catalog);
+    if items.len() > EXHAUSTIVE_MAX_ITEMS {
+        return Err(AppError::Optimization(format!(
+            "Solver failed: {} items are too many to enumerate",
+            items.len()
+        )));
+    }
+
+    let forced = problem.forced_choice();
+    let mut best: Option<(f64, Choice)> = None;
+
+    for mask in 0..(1u32 << items.len()) {
+        let mut choice = forced.clone();
+        for (index, item) in items.iter().enumerate() {
+            if mask & (1 << index) != 0 {
+                choice.add(item);
+            }
+        }
+
+        if !problem.is_feasible(&choice) || problem.is_cut(&choice.ids(service_catalog)) {
+            continue;
+        }
+
+        let objective = problem.objective(&choice);
+        if best
+            .as_ref()
+            .is_none_or(|(best_objective, _)| objective < *best_objective)
+        {
+            best = Some((objective, choice));
+        }
+    }
+
+    Ok(best.map(|(_, choice)| choice))
+}
+
+/// Plans, bundles, add-ons and one-offs the exhaustive backend may pick or leave out
+///
+/// Free sources are always kept, and items touching an excluded service are never
+/// picked, so neither is enumerated.
+fn optional_items(service_catalog: &ServiceCatalog) -> Vec<Choice> {
+    let excluded = &service_catalog.excluded;
+    let service_ids = service_catalog.service_ids();
+
+    let plans = service_catalog
+        .plans
+        .iter()
+        .filter(|plan| !excluded.contains(&plan.id))
+        .map(|plan| Choice {
+            plans: HashSet::from([plan.plan_id.clone()]),
+            ..Default::default()
+        });
+    let bundles = service_catalog
+        .bundles
+        .iter()
+        .filter(|bundle| {
+            !bundle
+                .members
+                .iter()
+                .any(|member| excluded.contains(&member.id))
+        })
+        .map(|bundle| Choice {
+            bundles: HashSet::from([bundle.id.clone()]),
+            ..Default::default()
+        });
+    let addons = service_catalog
+        .addons
+        .iter()
+        .filter(|addon| {
+            service_ids.contains(addon.parent_service_id.as_str())
+                && !excluded.contains(&addon.id)
+                && !excluded.contains(&addon.parent_service_id)
+        })
+        .map(|addon| addon.id.clone());
+    let one_offs = service_catalog
+        .one_offs
+        .iter()
+        .filter(|one_off| one_off.route != CoverageRoute::Free)
+        .map(|one_off| one_off.id.clone());
+    let extras = addons.chain(one_offs).map(|id| Choice {
+        extras: HashSet::from([id]),
+        ..Default::default()
+    });
+
+    plans.chain(bundles).chain(extras).collect()
+}
+
+impl CoverProblem<'_> {
+    /// Free sources, which every selection keeps
+    fn forced_choice(&self) -> Choice {
+        Choice {
+            plans: HashSet::new(),
+            bundles: HashSet::new(),
+            extras: self
+                .service_catalog
+                .one_offs
+                .iter()
+                .filter(|one_off| one_off.route == CoverageRoute::Free)
+                .map(|one_off| one_off.id.clone())
+                .collect(),
+        }
+    }
+
+    /// Keys of the available must-have titles with at least one known service
+    fn must_have_keys(&self) -> impl Iterator<Item = String> + '_ {
+        self.available_must_have
+            .iter()
+            .map(|title| title.to_string())
+            .filter(|key| self.title_to_services.contains_key(key))
+    }
+
+    /// Distinct keys of the nice-to-have titles with at least one known service
+    fn nice_to_have_keys(&self) -> HashSet<String> {
+        self.nice_to_have
+            .iter()
+            .map(|title| title.to_string())
+            .filter(|key| self.title_to_services.contains_key(key))
+            .collect()
+    }
+
+    /// Whether one of `ids` carries the title
+    fn covers(&self, ids: &HashSet<String>, title_key: &str) -> bool {
+        self.title_to_services
+            .get(title_key)
+            .is_some_and(|services| services.iter().any(|id| ids.contains(id)))
+    }
+
+    /// Priority-weighted nice-to-have coverage of the held services and one-offs
+    fn weighted_coverage(&self, ids: &HashSet<String>) -> f64 {
+        self.nice_to_have_keys()
+            .iter()
+            .filter(|key| self.covers(ids, key))
+            .map(|key| priority_for_key(self.priorities, key))
+            .sum()
+    }
+
+    /// The integer programming objective for a selection
+    fn objective(&self, choice: &Choice) -> f64 {
+        let max_weighted_coverage: f64 = self
+            .nice_to_have_keys()
+            .iter()
+            .map(|key| priority_for_key(self.priorities, key))
+            .sum();
+        let ids = choice.ids(self.service_catalog);
+
+        let free_plans = self
+            .service_catalog
+            .plans
+            .iter()
+            .filter(|plan| plan.cost.is_zero() && choice.plans.contains(&plan.plan_id))
+            .count();
+
+        solver_cost(choice.cost(self.service_catalog))
+            - COVERAGE_TIE_BREAK / (max_weighted_coverage + 1.0) * self.weighted_coverage(&ids)
+            - FREE_PLAN_BONUS * free_plans as f64
+            + self.held_penalty() * choice.held_count(self.service_catalog) as f64
+    }
+
+    /// What each held service adds to the objective under the simplicity tolerance
+    fn held_penalty(&self) -> f64 {
+        if self.simplicity_tolerance.is_zero() {
+            0.0
+        } else {
+            solver_cost(self.simplicity_tolerance) + SIMPLICITY_TIE_BREAK
+        }
+    }
+
+    /// Adds the zero-cost plans of services not held yet, as long as they fit under the
+    /// service cap and are worth more than the simplicity tolerance
+    fn keep_free_plans(&self, choice: &mut Choice) {
+        let service_catalog = self.service_catalog;
+        if self.held_penalty() >= FREE_PLAN_BONUS {
+            return;
+        }
+
+        for plan in &service_catalog.plans {
+            if !plan.cost.is_zero() || service_catalog.excluded.contains(&plan.id) {
+                continue;
+            }
+            if choice
+                .sources_per_service(service_catalog)
+                .contains_key(plan.id.as_str())
+            {
+                continue;
+            }
+            if service_catalog
+                .max_services
+                .is_some_and(|cap| choice.held_count(service_catalog) >= cap)
+            {
+                break;
+            }
+
+            let mut candidate = choice.clone();
+            candidate.plans.insert(plan.plan_id.clone());
+            if !self.is_cut(&candidate.ids(service_catalog)) {
+                *choice = candidate;
+            }
+        }
+    }
+
+    /// Whether a selection meets every constraint of the integer programming model other
+    /// than the no-good cuts
+    fn is_feasible(&self, choice: &Choice) -> bool {
+        let service_catalog = self.service_catalog;
+
+        // Each service is held through at most one standalone plan or bundle
+        let sources = choice.sources_per_service(service_catalog);
+        if sources.values().any(|&count| count > 1) {
+            return false;
+        }
+
+        // Free sources are always kept
+        let forced = self.forced_choice();
+        if !forced.extras.is_subset(&choice.extras) {
+            return false;
+        }
+
+        // Add-ons need their parent service
+        let orphaned_addon = service_catalog.addons.iter().any(|addon| {
+            choice.extras.contains(&addon.id)
+                && !sources.contains_key(addon.parent_service_id.as_str())
+        });
+        if orphaned_addon {
+            return false;
+        }
+
+        let ids = choice.ids(service_catalog);
+        if !service_catalog.required.is_subset(&ids)
+            || ids.iter().any(|id| service_catalog.excluded.contains(id))
+        {
+            return false;
+        }
+
+        if service_catalog
+            .max_services
+            .is_some_and(|cap| choice.held_count(service_catalog) > cap)
+        {
+            return false;
+        }
+
+        if !self.must_have_keys().all(|key| self.covers(&ids, &key)) {
+            return false;
+        }
+
+        self.cuts
+            .min_weighted_coverage
+            .is_none_or(|min_coverage| self.weighted_coverage(&ids) >= min_coverage)
+    }
+
+    /// Whether the selection was excluded by a no-good cut
+    fn is_cut(&self, ids: &HashSet<String>) -> bool {
+        self.cuts.excluded_selections.iter().any(|cut| cut == ids)
+    }
+
+    /// Steps that start holding something new without touching an excluded service
+    fn greedy_steps(&self, ids: &HashSet<String>) -> Vec<Step> {
+        let service_catalog = self.service_catalog;
+        let allowed = |id: &str| !ids.contains(id) && !service_catalog.excluded.contains(id);
+
+        // Cheapest standalone plan of every service, in catalog order
+        let mut cheapest_plans: Vec<&ServiceInfo> = Vec::new();
+        for plan in &service_catalog.plans {
+            match cheapest_plans
+                .iter_mut()
+                .find(|cheapest| cheapest.id == plan.id)
+            {
+                Some(cheapest) if plan.cost < cheapest.cost => *cheapest = plan,
+                Some(_) => {}
+                None => cheapest_plans.push(plan),
+            }
+        }
+
+        let mut steps: Vec<Step> = cheapest_plans
+            .iter()
+            .filter(|plan| allowed(&plan.id))
+            .map(|plan| Step::plan(plan))
+            .collect();
+
+        for bundle in &service_catalog.bundles {
+            if bundle.members.iter().all(|member| allowed(&member.id)) {
+                steps.push(Step {
+                    choice: Choice {
+                        bundles: HashSet::from([bundle.id.clone()]),
+                        ..Default::default()
+                    },
+                    ids: bundle.members.iter().map(|m| m.id.clone()).collect(),
+                    cost: solver_cost(bundle.cost),
+                    held: bundle.members.len(),
+                });
+            }
+        }
+
+        for addon in &service_catalog.addons {
+            if !allowed(&addon.id) {
+                continue;
+            }
+            if ids.contains(&addon.parent_service_id) {
+                steps.push(Step::extra(&addon.id, addon.cost, 1));
+            } else if allowed(&addon.parent_service_id) {
+                let parent = cheapest_plans
+                    .iter()
+                    .find(|plan| plan.id == addon.parent_service_id);
+                if let Some(parent) = parent {
+                    let mut step = Step::plan(parent);
+                    let addon_step = Step::extra(&addon.id, addon.cost, 1);
+                    step.choice.add(&addon_step.choice);
+                    step.ids.extend(addon_step.ids);
+                    step.cost += addon_step.cost;
+                    step.held += addon_step.held;
+                    steps.push(step);
+                }
+            }
+        }
+
+        for one_off in &service_catalog.one_offs {
+            if allowed(&one_off.id) {
+                steps.push(Step::extra(&one_off.id, one_off.price, 0));
+            }
+        }
+
+        steps
+    }
+
+    /// The best-ranked step with a positive gain that fits under the service cap
+    fn best_step(
+        &self,
+        choice: &Choice,
+        ids: &HashSet<String>,
+        rank: StepRank,
+        gain: impl Fn(&Step) -> f64,
+    ) -> Option<Step> {
+        let held = choice.held_count(self.service_catalog);
+        self.greedy_steps(ids)
+            .into_iter()
+            .filter(|step| {
+                self.service_catalog
+                    .max_services
+                    .is_none_or(|cap| held + step.held <= cap)
+            })
+            .filter_map(|step| {
+                let gain = gain(&step);
+                let cost = step.cost + self.held_penalty() * step.held as f64;
+                (gain > 0.0).then_some((gain, cost, step))
+            })
+            .min_by(|(gain_a, cost_a, _), (gain_b, cost_b, _)| match rank {
+                StepRank::CostPerGain => (cost_a / gain_a).total_cmp(&(cost_b / gain_b)),
+                StepRank::Gain => gain_b.total_cmp(gain_a).then(cost_a.total_cmp(cost_b)),
+            })
+            .map(|(_, _, step)| step)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn title(id: &str) -> TitleId {
+        TitleId::Imdb(id.to_string())
+    }
+
+    /// Netflix carries both titles; Hulu and Disney+ one each but cost less together
+    fn split_catalog() -> (ServiceCatalog, HashMap<String, Vec<String>>) {
+        let service_catalog = ServiceCatalog::from(vec![
+            ServiceInfo::single_plan("netflix", "Netflix", 15.49),
+            ServiceInfo::single_plan("hulu", "Hulu", 7.99),
+            ServiceInfo::single_plan("disney", "Disney+", 6.99),
+        ]);
+
+        let mut title_to_services = HashMap::new();
+        title_to_services.insert(
+            "tt1111111".to_string(),
+            vec!["netflix".to_string(), "hulu".to_string()],
+        );
+        title_to_services.insert(
+            "tt2222222".to_string(),
+            vec!["netflix".to_string(), "disney".to_string()],
+        );
+
+        (service_catalog, title_to_services)
+    }
+
+    fn options(backend: SolverBackend, deadline: Option<Instant>) -> SolveOptions {
+        SolveOptions {
+            backend,
+            simplicity_tolerance: Decimal::ZERO,
+            deadline,
+        }
+    }
+
+    #[test]
+    fn test_backends_agree_on_small_catalog() {
+        let (service_catalog, title_to_services) = split_catalog();
+        let must_have = [title("tt1111111"), title("tt2222222")];
+        let available_must_have: Vec<&TitleId> = must_have.iter().collect();
+        let cuts = SolutionCuts::default();
+        let problem = CoverProblem {
+            service_catalog: &service_catalog,
+            title_to_services: &title_to_services,
+            available_must_have: &available_must_have,
+            nice_to_have: &[],
+            priorities: &HashMap::new(),
+            cuts: &cuts,
+            simplicity_tolerance: Decimal::ZERO,
+        };
+
+        for backend in [
+            SolverBackend::Milp,
+            SolverBackend::Greedy,
+            SolverBackend::Exhaustive,
+        ] {
+            let solved = solve(&problem, &options(backend, None)).unwrap().unwrap();
+            assert_eq!(solved.backend, backend);
+            assert_eq!(solved.proven_optimal, backend != SolverBackend::Greedy);
+            assert_eq!(
+                solved.choice.ids(&service_catalog),
+                HashSet::from(["hulu".to_string(), "disney".to_string()])
+            );
+        }
+    }
+
+    #[test]
+    fn test_milp_falls_back_to_greedy_past_deadline() {
+        let (service_catalog, title_to_services) = split_catalog();
+        let must_have = [title("tt1111111")];
+        let available_must_have: Vec<&TitleId> = must_have.iter().collect();
+        let cuts = SolutionCuts::default();
+        let problem = CoverProblem {
+            service_catalog: &service_catalog,
+            title_to_services: &title_to_services,
+            available_must_have: &available_must_have,
+            nice_to_have: &[],
+            priorities: &HashMap::new(),
+            cuts: &cuts,
+            simplicity_tolerance: Decimal::ZERO,
+        };
+
+        let solved = solve(
+            &problem,
+            &options(SolverBackend::Milp, Some(Instant::now())),
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(solved.backend, SolverBackend::Greedy);
+        assert!(!solved.proven_optimal);
+        assert_eq!(
+            solved.choice.ids(&service_catalog),
+            HashSet::from(["hulu".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_greedy_retries_by_coverage_under_service_cap() {
+        // Disney+ is the cheapest per title, but taking it leaves no room for the other
+        let (mut service_catalog, title_to_services) = split_catalog();
+        service_catalog.max_services = Some(1);
+        let must_have = [title("tt1111111"), title("tt2222222")];
+        let available_must_have: Vec<&TitleId> = must_have.iter().collect();
+        let cuts = SolutionCuts::default();
+        let problem = CoverProblem {
+            service_catalog: &service_catalog,
+            title_to_services: &title_to_services,
+            available_must_have: &available_must_have,
+            nice_to_have: &[],
+            priorities: &HashMap::new(),
+            cuts: &cuts,
+            simplicity_tolerance: Decimal::ZERO,
+        };
+
+        let choice = greedy(&problem).unwrap();
+
+        assert_eq!(
+            choice.ids(&service_catalog),
+            HashSet::from(["netflix".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_solve_model_respects_deadline() {
+        let model = || {
+            let mut vars = ProblemVariables::new();
+            let x = vars.add(good_lp::variable().binary());
+            vars.maximise(x).using(default_solver)
+        };
+
+        let in_time = Instant::now() + Duration::from_secs(60);
+        assert!(matches!(
+            solve_model(model(), Some(in_time)),
+            Ok(MilpOutcome::Solved(_))
+        ));
+        assert!(matches!(
+            solve_model(model(), Some(Instant::now())),
+            Ok(MilpOutcome::TimedOut)
+        ));
+    }
+
+    #[test]
+    fn test_solve_options_reject_exhaustive_on_large_catalog() {
+        let service_catalog = ServiceCatalog::from(
+            (0..=EXHAUSTIVE_MAX_ITEMS)
+                .map(|i| ServiceInfo::single_plan(&format!("service{}", i), "Service", 9.99))
+                .collect::<Vec<_>>(),
+        );
+        let request = OptimizationRequest {
+            solver: SolverBackend::Exhaustive,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            SolveOptions::new(&request, &service_catalog),
+            Err(AppError::InvalidInput(_))
+        ));
+    }
+}
@@ -5,7 +5,7 @@
 /// both title search and availability lookup.
 use crate::{
     error::AppResult,
-    models::{StreamingAvailability, Title, TitleId},
+    models::{Country, StreamingAvailability, Title, TitleId},
 };
 
 pub mod streaming_availability;
@@ -18,18 +18,23 @@ pub mod watchmode;
 /// cost of converting between different provider ID systems.
 #[async_trait::async_trait]
 pub trait StreamingProvider: Send + Sync {
-    /// Search for titles by name
+    /// Search for titles by name in a country's catalog
     ///
     /// Returns a list of matching titles with IDs for downstream availability lookups.
-    async fn search_titles(&self, query: &str) -> AppResult<Vec<Title>>;
+    async fn search_titles(&self, query: &str, country: Country) -> AppResult<Vec<Title>>;
 
     /// Fetch streaming availability by title ID
     ///
     /// Accepts either IMDB ID or provider-specific ID. Provider-specific IDs may be more
     /// efficient (e.g., Watchmode charges less for native ID lookups vs IMDB ID lookups).
     ///
-    /// Returns availability data including which services have the title and pricing.
-    async fn fetch_availability(&self, title_id: &TitleId) -> AppResult<StreamingAvailability>;
+    /// Returns availability data including which services have the title and pricing
+    /// in the given country.
+    async fn fetch_availability(
+        &self,
+        title_id: &TitleId,
+        country: Country,
+    ) -> AppResult<StreamingAvailability>;
 
     /// Fetch availability for multiple titles in parallel
     ///
@@ -38,12 +43,14 @@ pub trait StreamingProvider: Send + Sync {
     async fn fetch_availability_batch(
         &self,
         title_ids: Vec<TitleId>,
+        country: Country,
     ) -> AppResult<Vec<StreamingAvailability>> {
         let mut tasks = Vec::new();
 
         for title_id in title_ids {
             let provider = self.clone_for_task();
-            let task = tokio::spawn(async move { provider.fetch_availability(&title_id).await });
+            let task =
+                tokio::spawn(async move { provider.fetch_availability(&title_id, country).await });
             tasks.push(task);
         }
 
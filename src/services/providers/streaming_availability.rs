@@ -7,8 +7,8 @@ use crate::{
     db::{Cache, CacheKey},
     error::{AppError, AppResult},
     models::{
-        ApiShow, ApiShowDetails, AvailabilityType, ServiceAvailability, StreamingAvailability,
-        Title, TitleId,
+        ApiShow, ApiShowDetails, AvailabilityType, Country, ServiceAvailability,
+        StreamingAvailability, Title, TitleId,
     },
     services::providers::StreamingProvider,
 };
@@ -18,7 +18,6 @@ use serde::Deserialize;
 
 const TITLE_CACHE_TTL: u64 = 3600; // 1 hour
 const AVAIL_CACHE_TTL: u64 = 604800; // 1 week
-const SEARCH_COUNTRY: &str = "us";
 
 #[derive(Debug, Deserialize)]
 struct ApiSearchResponse(Vec<ApiShow>);
@@ -41,15 +40,21 @@ impl StreamingAvailabilityProvider {
         }
     }
 
-    fn convert_api_response(&self, details: ApiShowDetails) -> AppResult<StreamingAvailability> {
+    fn convert_api_response(
+        &self,
+        details: ApiShowDetails,
+        country: Country,
+    ) -> AppResult<StreamingAvailability> {
         let imdb_id = details
             .imdb_id
             .ok_or_else(|| AppError::ExternalApi("API response missing IMDB ID".to_string()))?;
 
         let mut services = Vec::new();
 
-        if let Some(us_options) = details.streaming_options.get("us") {
-            for option in us_options {
+        // Streaming options are keyed by lowercase country code
+        let country_code = country.code().to_lowercase();
+        if let Some(options) = details.streaming_options.get(&country_code) {
+            for option in options {
                 let availability_type = match option.availability_type.as_str() {
                     "subscription" => AvailabilityType::Subscription,
                     "rent" => AvailabilityType::Rent,
@@ -63,6 +68,11 @@ impl StreamingAvailabilityProvider {
                     service_id: option.service.id.clone(),
                     service_name: option.service.name.clone(),
                     availability_type,
+                    addon_id: option.addon.as_ref().map(|addon| addon.id.clone()),
+                    price: option
+                        .price
+                        .as_ref()
+                        .and_then(|price| price.amount.parse().ok()),
                     quality: option.quality.clone(),
                     link: option.link.clone(),
                 });
@@ -79,7 +89,7 @@ impl StreamingAvailabilityProvider {
 
 #[async_trait::async_trait]
 impl StreamingProvider for StreamingAvailabilityProvider {
-    async fn search_titles(&self, query: &str) -> AppResult<Vec<Title>> {
+    async fn search_titles(&self, query: &str, country: Country) -> AppResult<Vec<Title>> {
         if query.trim().is_empty() {
             return Err(AppError::InvalidInput(
                 "Search query cannot be empty".to_string(),
@@ -88,7 +98,7 @@ impl StreamingProvider for StreamingAvailabilityProvider {
 
         cached!(
             self.cache,
-            CacheKey::TitleSearch(query.to_string()),
+            CacheKey::TitleSearch(country, query.to_string()),
             TITLE_CACHE_TTL,
             async move {
                 // Fetch from API
@@ -97,7 +107,10 @@ impl StreamingProvider for StreamingAvailabilityProvider {
                     .http_client
                     .get(&url)
                     .header("X-RapidAPI-Key", &self.api_key)
-                    .query(&[("title", query), ("country", SEARCH_COUNTRY)])
+                    .query(&[
+                        ("title", query),
+                        ("country", &country.code().to_lowercase()),
+                    ])
                     .send()
                     .await?;
 
@@ -115,6 +128,7 @@ impl StreamingProvider for StreamingAvailabilityProvider {
 
                 tracing::info!(
                     query = %query,
+                    country = %country,
                     results = titles.len(),
                     provider = "streaming_availability",
                     "Title search completed"
@@ -125,10 +139,14 @@ impl StreamingProvider for StreamingAvailabilityProvider {
         )
     }
 
-    async fn fetch_availability(&self, title_id: &TitleId) -> AppResult<StreamingAvailability> {
+    async fn fetch_availability(
+        &self,
+        title_id: &TitleId,
+        country: Country,
+    ) -> AppResult<StreamingAvailability> {
         cached!(
             self.cache,
-            CacheKey::Availability(format!("{}", title_id)),
+            CacheKey::Availability(country, format!("{}", title_id)),
             AVAIL_CACHE_TTL,
             async move {
                 // Fetch from API
@@ -137,7 +155,7 @@ impl StreamingProvider for StreamingAvailabilityProvider {
                     .http_client
                     .get(&url)
                     .header("X-RapidAPI-Key", &self.api_key)
-                    .query(&[("country", "us")]) // TODO: Add support for additional regions
+                    .query(&[("country", &country.code().to_lowercase())])
                     .send()
                     .await?;
 
@@ -151,10 +169,11 @@ impl StreamingProvider for StreamingAvailabilityProvider {
                 }
 
                 let show_details: ApiShowDetails = response.json().await?;
-                let availability = self.convert_api_response(show_details)?;
+                let availability = self.convert_api_response(show_details, country)?;
 
                 tracing::info!(
                     title_id = %title_id,
+                    country = %country,
                     services = availability.services.len(),
                     provider = "streaming_availability",
                     "Availability fetched"
@@ -174,6 +193,7 @@ impl StreamingProvider for StreamingAvailabilityProvider {
 mod tests {
     use super::*;
     use crate::models::ApiStreamingOption;
+    use rust_decimal::Decimal;
     use std::collections::HashMap;
 
     async fn create_test_provider() -> StreamingAvailabilityProvider {
@@ -200,6 +220,8 @@ mod tests {
                     name: "Netflix".to_string(),
                 },
                 availability_type: "subscription".to_string(),
+                addon: None,
+                price: None,
                 quality: Some("4K".to_string()),
                 link: Some("https://netflix.com/title/123".to_string()),
             }],
@@ -210,7 +232,7 @@ mod tests {
             streaming_options,
         };
 
-        let result = provider.convert_api_response(details).unwrap();
+        let result = provider.convert_api_response(details, Country::Us).unwrap();
 
         assert_eq!(result.id, TitleId::Imdb("tt1375666".to_string()));
         assert_eq!(result.services.len(), 1);
@@ -223,6 +245,36 @@ mod tests {
         assert_eq!(result.services[0].quality, Some("4K".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_convert_api_response_uses_requested_country() {
+        let provider = create_test_provider().await;
+
+        let option = |service_id: &str| ApiStreamingOption {
+            service: crate::models::ApiService {
+                id: service_id.to_string(),
+                name: service_id.to_string(),
+            },
+            availability_type: "subscription".to_string(),
+            addon: None,
+            price: None,
+            quality: None,
+            link: None,
+        };
+        let mut streaming_options = HashMap::new();
+        streaming_options.insert("us".to_string(), vec![option("hulu")]);
+        streaming_options.insert("ca".to_string(), vec![option("crave")]);
+
+        let details = ApiShowDetails {
+            imdb_id: Some("tt1375666".to_string()),
+            streaming_options,
+        };
+
+        let result = provider.convert_api_response(details, Country::Ca).unwrap();
+
+        assert_eq!(result.services.len(), 1);
+        assert_eq!(result.services[0].service_id, "crave");
+    }
+
     #[tokio::test]
     async fn test_convert_api_response_missing_imdb_id() {
         let provider = create_test_provider().await;
@@ -232,7 +284,7 @@ mod tests {
             streaming_options: HashMap::new(),
         };
 
-        let result = provider.convert_api_response(details);
+        let result = provider.convert_api_response(details, Country::Us);
         assert!(result.is_err());
     }
 
@@ -250,6 +302,8 @@ mod tests {
                         name: "Netflix".to_string(),
                     },
                     availability_type: "subscription".to_string(),
+                    addon: None,
+                    price: None,
                     quality: Some("HD".to_string()),
                     link: None,
                 },
@@ -259,6 +313,8 @@ mod tests {
                         name: "iTunes".to_string(),
                     },
                     availability_type: "rent".to_string(),
+                    addon: None,
+                    price: None,
                     quality: Some("HD".to_string()),
                     link: None,
                 },
@@ -268,6 +324,8 @@ mod tests {
                         name: "Vudu".to_string(),
                     },
                     availability_type: "buy".to_string(),
+                    addon: None,
+                    price: None,
                     quality: Some("HD".to_string()),
                     link: None,
                 },
@@ -279,7 +337,7 @@ mod tests {
             streaming_options,
         };
 
-        let result = provider.convert_api_response(details).unwrap();
+        let result = provider.convert_api_response(details, Country::Us).unwrap();
 
         assert_eq!(result.services.len(), 3);
         assert_eq!(
@@ -289,4 +347,76 @@ mod tests {
         assert_eq!(result.services[1].availability_type, AvailabilityType::Rent);
         assert_eq!(result.services[2].availability_type, AvailabilityType::Buy);
     }
+
+    #[tokio::test]
+    async fn test_convert_api_response_keeps_addon_channel() {
+        let provider = create_test_provider().await;
+
+        let mut streaming_options = HashMap::new();
+        streaming_options.insert(
+            "us".to_string(),
+            vec![ApiStreamingOption {
+                service: crate::models::ApiService {
+                    id: "prime".to_string(),
+                    name: "Prime Video".to_string(),
+                },
+                availability_type: "addon".to_string(),
+                addon: Some(crate::models::ApiAddon {
+                    id: "starz".to_string(),
+                }),
+                price: None,
+                quality: Some("HD".to_string()),
+                link: None,
+            }],
+        );
+
+        let details = ApiShowDetails {
+            imdb_id: Some("tt1375666".to_string()),
+            streaming_options,
+        };
+
+        let result = provider.convert_api_response(details, Country::Us).unwrap();
+
+        assert_eq!(result.services.len(), 1);
+        assert_eq!(result.services[0].service_id, "prime");
+        assert_eq!(
+            result.services[0].availability_type,
+            AvailabilityType::Addon
+        );
+        assert_eq!(result.services[0].addon_id, Some("starz".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_convert_api_response_parses_rental_price() {
+        let provider = create_test_provider().await;
+
+        let mut streaming_options = HashMap::new();
+        streaming_options.insert(
+            "us".to_string(),
+            vec![ApiStreamingOption {
+                service: crate::models::ApiService {
+                    id: "apple".to_string(),
+                    name: "Apple TV".to_string(),
+                },
+                availability_type: "rent".to_string(),
+                addon: None,
+                price: Some(crate::models::ApiPrice {
+                    amount: "3.99".to_string(),
+                }),
+                quality: Some("HD".to_string()),
+                link: None,
+            }],
+        );
+
+        let details = ApiShowDetails {
+            imdb_id: Some("tt1375666".to_string()),
+            streaming_options,
+        };
+
+        let result = provider.convert_api_response(details, Country::Us).unwrap();
+
+        assert_eq!(result.services.len(), 1);
+        assert_eq!(result.services[0].availability_type, AvailabilityType::Rent);
+        assert_eq!(result.services[0].price, Some(Decimal::new(399, 2)));
+    }
 }
@@ -18,7 +18,7 @@ use crate::{
     db::{Cache, CacheKey},
     error::{AppError, AppResult},
     models::{
-        AvailabilityType, ServiceAvailability, StreamingAvailability, Title, TitleId,
+        AvailabilityType, Country, ServiceAvailability, StreamingAvailability, Title, TitleId,
         WatchmodeTitle, WatchmodeTitleDetails,
     },
     services::providers::StreamingProvider,
@@ -173,7 +173,7 @@ impl WatchmodeProvider {
 
 #[async_trait::async_trait]
 impl StreamingProvider for WatchmodeProvider {
-    async fn search_titles(&self, query: &str) -> AppResult<Vec<Title>> {
+    async fn search_titles(&self, query: &str, country: Country) -> AppResult<Vec<Title>> {
         if query.trim().is_empty() {
             return Err(AppError::InvalidInput(
                 "Search query cannot be empty".to_string(),
@@ -182,7 +182,7 @@ impl StreamingProvider for WatchmodeProvider {
 
         cached!(
             self.cache,
-            CacheKey::TitleSearch(query.to_string()),
+            CacheKey::TitleSearch(country, query.to_string()),
             TITLE_CACHE_TTL,
             async move {
                 // Fetch from API
@@ -195,6 +195,7 @@ impl StreamingProvider for WatchmodeProvider {
                         ("apiKey", self.api_key.as_str()),
                         ("search_value", query),
                         ("search_type", "1"), // 1 = movies and TV
+                        ("regions", country.code()),
                     ])
                     .send()
                     .await?;
@@ -253,7 +254,11 @@ impl StreamingProvider for WatchmodeProvider {
         )
     }
 
-    async fn fetch_availability(&self, title_id: &TitleId) -> AppResult<StreamingAvailability> {
+    async fn fetch_availability(
+        &self,
+        title_id: &TitleId,
+        country: Country,
+    ) -> AppResult<StreamingAvailability> {
         // Capture the original requested TitleId so we can return the availability
         // using the original ID (IMDB or Watchmode). This ensures callers who
         // requested by IMDB can still look up availability by that IMDB ID even
@@ -273,7 +278,7 @@ impl StreamingProvider for WatchmodeProvider {
 
         cached!(
             self.cache,
-            CacheKey::Availability(cache_key.clone()),
+            CacheKey::Availability(country, cache_key.clone()),
             AVAIL_CACHE_TTL,
             async move {
                 // Fetch title details with sources
@@ -285,7 +290,7 @@ impl StreamingProvider for WatchmodeProvider {
                     .query(&[
                         ("apiKey", self.api_key.as_str()),
                         ("append_to_response", "sources"),
-                        ("regions", "US"), // TODO: Add support for additional regions
+                        ("regions", country.code()),
                     ])
                     .send()
                     .await?;
@@ -320,6 +325,7 @@ impl StreamingProvider for WatchmodeProvider {
                 tracing::info!(
                     requested_id = %requested_id,
                     watchmode_id = watchmode_id,
+                    country = %country,
                     services = availability.services.len(),
                     provider = "watchmode",
                     "Availability fetched"
@@ -352,10 +358,13 @@ impl WatchmodeProvider {
                     if let Some(availability_type) =
                         self.parse_availability_type(&source.source_type)
                     {
+                        // Watchmode lists channels as sources of their own, without a parent
                         services.push(ServiceAvailability {
                             service_id,
                             service_name,
                             availability_type,
+                            addon_id: None,
+                            price: source.price,
                             quality: source.format,
                             link: source.web_url,
                         });
@@ -440,6 +449,7 @@ mod tests {
                 web_url: Some("https://netflix.example".to_string()),
                 source_type: "sub".to_string(),
                 format: Some("HD".to_string()),
+                price: None,
             }]),
         };
 
@@ -462,6 +472,7 @@ mod tests {
                 web_url: Some("https://netflix.example".to_string()),
                 source_type: "subscription".to_string(),
                 format: None,
+                price: None,
             }]),
         };
 
@@ -1,4 +1,8 @@
-use crate::{error::AppResult, models::Title, services::providers::StreamingProvider};
+use crate::{
+    error::AppResult,
+    models::{Country, Title},
+    services::providers::StreamingProvider,
+};
 use std::sync::Arc;
 
 /// Service function for title search
@@ -8,6 +12,7 @@ use std::sync::Arc;
 pub async fn search_titles(
     provider: Arc<dyn StreamingProvider>,
     query: &str,
+    country: Country,
 ) -> AppResult<Vec<Title>> {
-    provider.search_titles(query).await
+    provider.search_titles(query, country).await
 }